@@ -0,0 +1,67 @@
+//! Round-trips requests through the channel, to compare the queue [backends](bidirectional_channel::backend).
+//!
+//! ```text
+//! cargo bench --bench backend
+//! cargo bench --bench backend --features flume
+//! cargo bench --bench backend --features crossbeam
+//! ```
+
+use bidirectional_channel::{bounded, Responder};
+use futures::future::join_all;
+use std::time::{Duration, Instant};
+
+const REQUESTS: usize = 100_000;
+
+fn backend() -> &'static str {
+    if cfg!(feature = "crossbeam") {
+        "crossbeam"
+    } else if cfg!(feature = "flume") {
+        "flume"
+    } else {
+        "async-channel"
+    }
+}
+
+/// Send [`REQUESTS`] from `requesters` tasks to `responders` tasks, waiting for every response
+async fn round_trips(capacity: usize, requesters: usize, responders: usize) -> Duration {
+    let (requester, responder) = bounded::<usize, usize>(capacity);
+    let serving = (0..responders)
+        .map(|_| async_std::task::spawn(serve(responder.clone())))
+        .collect::<Vec<_>>();
+    drop(responder);
+    let start = Instant::now();
+    join_all((0..requesters).map(|_| {
+        let requester = requester.clone();
+        async_std::task::spawn(async move {
+            for request in 0..REQUESTS / requesters {
+                assert!(requester.send(request).await == Ok(request));
+            }
+        })
+    }))
+    .await;
+    let elapsed = start.elapsed();
+    drop(requester);
+    join_all(serving).await;
+    elapsed
+}
+
+async fn serve(responder: Responder<usize, usize>) {
+    while let Ok(received) = responder.recv().await {
+        let request = received.request;
+        let _ = received.respond(request);
+    }
+}
+
+fn main() {
+    println!("backend: {}", backend());
+    for (capacity, requesters, responders) in [(1, 1, 1), (64, 1, 1), (64, 8, 1), (64, 8, 8)] {
+        let elapsed = async_std::task::block_on(round_trips(capacity, requesters, responders));
+        println!(
+            "capacity {:>2}, {} requesters, {} responders: {:>6.0}ns per request",
+            capacity,
+            requesters,
+            responders,
+            elapsed.as_nanos() as f64 / REQUESTS as f64,
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Write a request handler as an [`Actor`], with state that lives across requests, and run it with [`serve`] or [`spawn`].
+//! [`spawn`] returns a [`Handle`], to [`ask`](Handle::ask) the actor for a reply, or [`tell`](Handle::tell) it something.
+//!
+//! ```
+//! use bidirectional_channel::{actor::{self, Actor}, spawn::AsyncStd};
+//! struct Counter(usize);
+//! impl Actor for Counter {
+//!     type Message = usize;
+//!     type Reply = usize;
+//!     async fn handle(&mut self, by: usize) -> usize {
+//!         self.0 += by;
+//!         self.0
+//!     }
+//! }
+//! # async_std::task::block_on(async {
+//! let counter = actor::spawn(Counter(0), 1, AsyncStd);
+//! assert!(counter.ask(1).await.unwrap() == 1);
+//! counter.tell(2).await.unwrap();
+//! assert!(counter.ask(0).await.unwrap() == 3);
+//! # })
+//! ```
+
+use crate::{spawn::Spawn, EnqueueError, Requester, Responder, SendRequestError};
+use derive_more::Deref;
+use std::future::Future;
+
+/// Handles requests one at a time, with exclusive access to its own state
+pub trait Actor {
+    /// The messages this actor handles
+    type Message;
+    /// What it replies with
+    type Reply;
+    /// Handle a message, returning the reply.
+    /// Implement this with an `async fn`
+    fn handle(&mut self, msg: Self::Message) -> impl Future<Output = Self::Reply> + Send;
+}
+
+/// Respond to each request on the channel with the output of [`Actor::handle`], in order.
+/// Returns the actor once every [`Requester`] was dropped, and the channel is empty.
+/// If a [`Requester`] stops waiting for its response, the actor still handles the request
+pub async fn serve<A: Actor>(mut actor: A, responder: Responder<A::Message, A::Reply>) -> A {
+    while let Ok(received) = responder.recv().await {
+        let (request, unresponded) = received.into();
+        let response = actor.handle(request).await;
+        let _ = unresponded.respond(response);
+    }
+    actor
+}
+
+/// A client for an [`Actor`], returned by [`spawn`].
+/// This is a [`Requester`] underneath, which it dereferences to for everything else
+#[derive(Deref)]
+pub struct Handle<A: Actor> {
+    requester: Requester<A::Message, A::Reply>,
+}
+
+impl<A: Actor> Handle<A> {
+    /// Talk to the actor serving `requester`'s channel
+    pub fn new(requester: Requester<A::Message, A::Reply>) -> Self {
+        Self { requester }
+    }
+
+    /// Send the actor a message, and wait for its reply.
+    /// Fails like [`Requester::send`]
+    pub async fn ask(&self, msg: A::Message) -> Result<A::Reply, SendRequestError<A::Message>> {
+        self.requester.send(msg).await
+    }
+
+    /// Send the actor a message without waiting for it to be handled, so the reply is discarded.
+    /// Fails like [`Requester::send_and_forget`]
+    pub async fn tell(&self, msg: A::Message) -> Result<(), EnqueueError<A::Message>> {
+        self.requester.send_and_forget(msg).await
+    }
+
+    /// Recover the underlying [`Requester`]
+    pub fn into_inner(self) -> Requester<A::Message, A::Reply> {
+        self.requester
+    }
+}
+
+// The actor needn't be `Clone` for its handle to be
+impl<A: Actor> Clone for Handle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            requester: self.requester.clone(),
+        }
+    }
+}
+
+/// Run `actor` in the background with `spawner`, on a channel of `capacity`.
+/// It stops once the returned [`Handle`], and every clone of it, is dropped.
+/// See [`serve`]
+pub fn spawn<A>(actor: A, capacity: usize, spawner: impl Spawn) -> Handle<A>
+where
+    A: Actor + Send + 'static,
+    A::Message: Send + 'static,
+    A::Reply: Send + 'static,
+{
+    let (requester, responder) = crate::bounded(capacity);
+    spawner.spawn(async move {
+        serve(actor, responder).await;
+    });
+    Handle::new(requester)
+}
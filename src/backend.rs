@@ -0,0 +1,117 @@
+//! The queue that requests wait on between a [`Requester`](crate::Requester) and a [`Responder`](crate::Responder).
+//!
+//! The channel enforces its own capacity, closes itself, and wakes waiting responders, so all it needs underneath is an unbounded queue which never waits.
+//! That's what [`ChannelBackend`] abstracts, and the handle types are the same whichever backend is used.
+//!
+//! [`AsyncChannel`] is used by default.
+//! The `flume` and `crossbeam` features swap in [`Flume`] or [`Crossbeam`] instead, for every channel the crate creates.
+//! If more than one is enabled, `crossbeam` is preferred over `flume`.
+//!
+//! Compare them for your access pattern with `cargo bench --bench backend`, adding e.g. `--features flume`.
+
+/// An unbounded multi-producer, multi-consumer queue, with the channel's close and wakeups built on top
+pub trait ChannelBackend<T> {
+    /// Create an empty queue
+    fn unbounded() -> Self;
+    /// Put an item at the back of the queue
+    fn send(&self, item: T);
+    /// Take the item at the front of the queue, without waiting if there isn't one
+    fn try_recv(&self) -> Option<T>;
+    /// How many items are on the queue
+    fn len(&self) -> usize;
+    /// Whether there are no items on the queue
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Backed by [`async_channel`], the channel async-std uses.
+/// This is the default
+pub struct AsyncChannel<T> {
+    sender: async_channel::Sender<T>,
+    receiver: async_channel::Receiver<T>,
+}
+
+impl<T> ChannelBackend<T> for AsyncChannel<T> {
+    fn unbounded() -> Self {
+        let (sender, receiver) = async_channel::unbounded();
+        Self { sender, receiver }
+    }
+
+    fn send(&self, item: T) {
+        // Neither end is closed or dropped while we hold both
+        let _ = self.sender.try_send(item);
+    }
+
+    fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn len(&self) -> usize {
+        self.receiver.len()
+    }
+}
+
+/// Backed by [`flume`], behind the `flume` feature
+#[cfg(feature = "flume")]
+pub struct Flume<T> {
+    sender: flume::Sender<T>,
+    receiver: flume::Receiver<T>,
+}
+
+#[cfg(feature = "flume")]
+impl<T> ChannelBackend<T> for Flume<T> {
+    fn unbounded() -> Self {
+        let (sender, receiver) = flume::unbounded();
+        Self { sender, receiver }
+    }
+
+    fn send(&self, item: T) {
+        // We hold the receiver, so it can't disconnect
+        let _ = self.sender.send(item);
+    }
+
+    fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn len(&self) -> usize {
+        self.receiver.len()
+    }
+}
+
+/// Backed by [`crossbeam_channel`], behind the `crossbeam` feature
+#[cfg(feature = "crossbeam")]
+pub struct Crossbeam<T> {
+    sender: crossbeam_channel::Sender<T>,
+    receiver: crossbeam_channel::Receiver<T>,
+}
+
+#[cfg(feature = "crossbeam")]
+impl<T> ChannelBackend<T> for Crossbeam<T> {
+    fn unbounded() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self { sender, receiver }
+    }
+
+    fn send(&self, item: T) {
+        // We hold the receiver, so it can't disconnect
+        let _ = self.sender.send(item);
+    }
+
+    fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn len(&self) -> usize {
+        self.receiver.len()
+    }
+}
+
+/// The backend every channel is created with, chosen by feature
+#[cfg(feature = "crossbeam")]
+pub(crate) type Selected<T> = Crossbeam<T>;
+#[cfg(all(feature = "flume", not(feature = "crossbeam")))]
+pub(crate) type Selected<T> = Flume<T>;
+#[cfg(not(any(feature = "flume", feature = "crossbeam")))]
+pub(crate) type Selected<T> = AsyncChannel<T>;
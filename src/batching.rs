@@ -0,0 +1,392 @@
+//! Coalesce many small requests into batches, for a [`Responder`](crate::Responder) which handles a `Vec` of requests at once.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, batching::BatchingRequester};
+//! use std::time::Duration;
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<Vec<&str>, Vec<usize>>(1);
+//! let batching = BatchingRequester::new(requester, 2, Duration::from_millis(10));
+//! let handler = async {
+//!     let batch = responder.recv().await.unwrap();
+//!     assert!(batch.len() == 2);
+//!     let lens = batch.iter().map(|request| request.len()).collect();
+//!     batch.respond(lens).unwrap();
+//! };
+//! let ((first, second), _) = join!(async { join!(batching.send("a"), batching.send("bb")) }, handler);
+//! assert!(first.unwrap() == 1 && second.unwrap() == 2);
+//! # })
+//! ```
+
+use crate::{
+    lock, EnqueueError, ReceivedRequest, Reply, Requester, Response, ResponseError,
+    SendRequestError,
+};
+use async_channel as channel;
+use event_listener::Event;
+use futures::{
+    channel::oneshot,
+    future::{select, Either, FusedFuture as _, Map, Shared},
+    FutureExt as _,
+};
+use futures_timer::Delay;
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Error returned by [`BatchingRequester::send`]
+#[derive(Error)]
+pub enum BatchError<Req> {
+    /// The batch failed to send, or wasn't responded to.
+    /// Returns ownership of the `Req` that failed to send, if the batch never reached the channel
+    #[error(transparent)]
+    Send(SendRequestError<Req>),
+    /// The [`Responder`](crate::Responder) responded to the batch with the wrong number of responses,
+    /// so they can't be matched up with the requests
+    #[error("The batch of {expected} requests got {actual} responses")]
+    WrongLength {
+        /// How many requests were in the batch
+        expected: usize,
+        /// How many responses came back
+        actual: usize,
+    },
+}
+impl<Req> Debug for BatchError<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Send(e) => f.debug_tuple("Send").field(e).finish(),
+            Self::WrongLength { expected, actual } => f
+                .debug_struct("WrongLength")
+                .field("expected", expected)
+                .field("actual", actual)
+                .finish(),
+        }
+    }
+}
+
+type Scattered<Resp> = Result<Mutex<Vec<Option<Resp>>>, ResponseError>;
+
+/// The responses to a batch on the channel.
+/// Every caller in the batch holds a clone, so any of them can drive it
+type Responses<Resp> =
+    Shared<Map<Response<Vec<Resp>>, fn(Result<Vec<Resp>, ResponseError>) -> Arc<Scattered<Resp>>>>;
+
+/// What a caller is told once its batch has gone out
+enum Ticket<Req, Resp> {
+    /// The batch never reached the channel
+    Failed(BatchError<Req>),
+    /// The batch is on the channel, and this caller's response will be at `index`
+    Sent {
+        responses: Responses<Resp>,
+        index: usize,
+        expected: usize,
+    },
+}
+
+type Waiter<Req, Resp> = oneshot::Sender<Ticket<Req, Resp>>;
+
+/// Requests waiting to be sent together
+struct Batch<Req, Resp> {
+    requests: Vec<Req>,
+    /// In the same order as the requests
+    waiters: Vec<Waiter<Req, Resp>>,
+}
+
+struct Buffer<Req, Resp> {
+    batch: Batch<Req, Resp>,
+    /// Counts up each time a batch is taken, so a caller can tell if its batch is still waiting
+    generation: u64,
+    /// When the first request of the batch was buffered
+    started: Instant,
+    /// Batches whose sender was cancelled before they reached the channel, for another caller to pick up
+    orphans: Vec<Prepared<Req, Resp>>,
+}
+
+/// Buffers individual requests, and sends them as one `Vec` once `max_batch` are waiting, or the first has waited for `max_delay`.
+/// Each caller still awaits its own response, which is picked out of the `Vec` of responses by index.
+///
+/// Batches are sent by the callers' futures, so a batch goes out as long as any of its callers are still waiting.
+/// If a caller's future is dropped while it's sending a batch, any other waiting caller takes over.
+/// Every caller borrows the [`BatchingRequester`], so by the time it's dropped, nobody is waiting on what's still buffered.
+/// Those requests are put on the channel if there's room, without waiting, and their responses are discarded.
+/// Use [`BatchingRequester::flush`] to send what's buffered straight away
+pub struct BatchingRequester<Req, Resp> {
+    requester: Requester<Vec<Req>, Vec<Resp>>,
+    max_batch: usize,
+    max_delay: Duration,
+    buffer: Mutex<Buffer<Req, Resp>>,
+    /// Notified when a batch is orphaned
+    orphaned: Event,
+}
+
+impl<Req, Resp> BatchingRequester<Req, Resp> {
+    /// Batch requests for `requester`, up to `max_batch` at a time, holding each for at most `max_delay`
+    ///
+    /// # Panics
+    /// If `max_batch` is zero
+    pub fn new(
+        requester: Requester<Vec<Req>, Vec<Resp>>,
+        max_batch: usize,
+        max_delay: Duration,
+    ) -> Self {
+        assert!(max_batch > 0, "max_batch cannot be zero");
+        Self {
+            requester,
+            max_batch,
+            max_delay,
+            buffer: Mutex::new(Buffer {
+                batch: Batch {
+                    requests: Vec::new(),
+                    waiters: Vec::new(),
+                },
+                generation: 0,
+                started: Instant::now(),
+                orphans: Vec::new(),
+            }),
+            orphaned: Event::new(),
+        }
+    }
+
+    /// Make a request as part of the next batch.
+    /// `await` the result to receive its response.
+    /// Fails like [`Requester::send`] if the batch fails, returning your request back if the batch never reached the channel,
+    /// or with [`BatchError::WrongLength`] if the batch's responses can't be matched up with its requests
+    pub async fn send(&self, request: Req) -> Result<Resp, BatchError<Req>> {
+        let (waiter, mut ticket) = oneshot::channel();
+        let (generation, deadline, full) = {
+            let mut buffer = lock(&self.buffer);
+            if buffer.batch.requests.is_empty() {
+                buffer.started = Instant::now();
+            }
+            buffer.batch.requests.push(request);
+            buffer.batch.waiters.push(waiter);
+            let full = buffer.batch.requests.len() >= self.max_batch;
+            (
+                buffer.generation,
+                buffer.started + self.max_delay,
+                full.then(|| take(&mut buffer)),
+            )
+        };
+        if let Some(batch) = full {
+            self.send_batch(self.prepare(batch)).await
+        }
+        let mut timeout = Delay::new(deadline.saturating_duration_since(Instant::now())).fuse();
+        let ticket = loop {
+            // An orphan may arrive before we start listening, so check after
+            let orphaned = self.orphaned.listen();
+            let batch = {
+                let mut buffer = lock(&self.buffer);
+                match buffer.orphans.pop() {
+                    Some(orphan) => Some(orphan),
+                    // Nobody has sent our batch in time, so send it ourselves
+                    None => (timeout.is_terminated() && buffer.generation == generation)
+                        .then(|| self.prepare(take(&mut buffer))),
+                }
+            };
+            if let Some(batch) = batch {
+                self.send_batch(batch).await;
+                continue;
+            }
+            match select(&mut ticket, select(&mut timeout, orphaned)).await {
+                Either::Left((ticket, _)) => break ticket,
+                Either::Right(_) => continue,
+            }
+        };
+        match ticket {
+            Ok(Ticket::Failed(e)) => Err(e),
+            Ok(Ticket::Sent {
+                responses,
+                index,
+                expected,
+            }) => match &*responses.await {
+                Ok(scattered) => {
+                    let mut scattered = lock(scattered);
+                    match scattered.len() {
+                        actual if actual != expected => {
+                            Err(BatchError::WrongLength { expected, actual })
+                        }
+                        _ => Ok(scattered[index]
+                            .take()
+                            .expect("each response is only taken by its own caller")),
+                    }
+                }
+                Err(e) => Err(BatchError::Send((*e).into())),
+            },
+            Err(oneshot::Canceled) => Err(BatchError::Send(SendRequestError::Ignored)),
+        }
+    }
+
+    /// Send every buffered request now, without waiting for the batch to fill up
+    pub async fn flush(&self) {
+        let batch = {
+            let mut buffer = lock(&self.buffer);
+            (!buffer.batch.requests.is_empty()).then(|| take(&mut buffer))
+        };
+        if let Some(batch) = batch {
+            self.send_batch(self.prepare(batch)).await
+        }
+    }
+
+    /// How many requests are buffered, waiting for the next batch
+    pub fn len(&self) -> usize {
+        lock(&self.buffer).batch.requests.len()
+    }
+
+    /// Whether no requests are buffered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Recover the underlying [`Requester`], after putting what's buffered on the channel like dropping would
+    pub fn into_inner(self) -> Requester<Vec<Req>, Vec<Resp>> {
+        self.requester.clone()
+    }
+
+    /// Wrap `batch` for the channel, once, so that waiting for space doesn't count or measure it again
+    fn prepare(&self, batch: Batch<Req, Resp>) -> Prepared<Req, Resp> {
+        let (received, response_receiver) =
+            self.requester.prepare_counted(batch.requests, (), |_| ());
+        Prepared {
+            response_receiver,
+            received,
+            waiters: batch.waiters,
+        }
+    }
+
+    /// Put `prepared` on the channel, waiting while it's full, and tell its callers where to find their responses.
+    /// If this is cancelled first, the batch is orphaned instead of lost
+    async fn send_batch(&self, prepared: Prepared<Req, Resp>) {
+        let mut sending = Sending {
+            batching: self,
+            prepared: Some(prepared),
+        };
+        let mut space = None;
+        loop {
+            let prepared = sending
+                .prepared
+                .take()
+                .expect("batch is only taken once it's sent");
+            sending.prepared = self.try_put(prepared, true);
+            if sending.prepared.is_none() {
+                return;
+            }
+            match space.take() {
+                // Space may have been made before we started listening, so check again
+                None => space = Some(self.requester.outgoing.listen()),
+                Some(space) => space.await,
+            }
+        }
+    }
+
+    /// Put `prepared` on the channel if there's room, and tell its callers how it went.
+    /// Gives it back if the channel is full, and `wait`ing for space is allowed
+    fn try_put(&self, prepared: Prepared<Req, Resp>, wait: bool) -> Option<Prepared<Req, Resp>> {
+        let Prepared {
+            response_receiver,
+            received,
+            waiters,
+        } = prepared;
+        match self.requester.outgoing.try_send(received) {
+            Ok(()) => tell(waiters, Ok(Response::new(response_receiver))),
+            Err(channel::TrySendError::Full(received))
+                if wait && !self.requester.outgoing.rejects() =>
+            {
+                return Some(Prepared {
+                    response_receiver,
+                    received,
+                    waiters,
+                })
+            }
+            Err(e) => tell(waiters, Err(EnqueueError::refused(e))),
+        }
+        None
+    }
+}
+
+/// Put what's buffered on the channel if there's room, since nobody is left to wait for it
+impl<Req, Resp> Drop for BatchingRequester<Req, Resp> {
+    fn drop(&mut self) {
+        let mut buffer = lock(&self.buffer);
+        let current = take(&mut buffer);
+        let mut batches = std::mem::take(&mut buffer.orphans);
+        if !current.requests.is_empty() {
+            batches.push(self.prepare(current))
+        }
+        for prepared in batches {
+            self.try_put(prepared, false);
+        }
+    }
+}
+
+/// A batch wrapped for the channel
+struct Prepared<Req, Resp> {
+    /// Dropped before the request, so that a batch which never reached the channel isn't warned about
+    response_receiver: oneshot::Receiver<Reply<Vec<Resp>>>,
+    received: ReceivedRequest<Vec<Req>, Vec<Resp>>,
+    waiters: Vec<Waiter<Req, Resp>>,
+}
+
+/// A batch on its way to the channel, which is orphaned if the caller sending it is cancelled
+struct Sending<'a, Req, Resp> {
+    batching: &'a BatchingRequester<Req, Resp>,
+    prepared: Option<Prepared<Req, Resp>>,
+}
+
+impl<Req, Resp> Drop for Sending<'_, Req, Resp> {
+    fn drop(&mut self) {
+        if let Some(prepared) = self.prepared.take() {
+            lock(&self.batching.buffer).orphans.push(prepared);
+            self.batching.orphaned.notify(usize::MAX);
+        }
+    }
+}
+
+/// Tell each caller how their batch went
+fn tell<Req, Resp>(
+    waiters: Vec<Waiter<Req, Resp>>,
+    sent: Result<Response<Vec<Resp>>, EnqueueError<Vec<Req>>>,
+) {
+    let expected = waiters.len();
+    match sent {
+        Ok(response) => {
+            let scatter: fn(_) -> _ = |responses: Result<Vec<Resp>, ResponseError>| {
+                Arc::new(
+                    responses
+                        .map(|responses| Mutex::new(responses.into_iter().map(Some).collect())),
+                )
+            };
+            let responses = response.map(scatter).shared();
+            for (index, waiter) in waiters.into_iter().enumerate() {
+                let _ = waiter.send(Ticket::Sent {
+                    responses: responses.clone(),
+                    index,
+                    expected,
+                });
+            }
+        }
+        Err(EnqueueError::Closed(requests)) => {
+            for (waiter, request) in waiters.into_iter().zip(requests) {
+                let e = SendRequestError::Closed(request);
+                let _ = waiter.send(Ticket::Failed(BatchError::Send(e)));
+            }
+        }
+        Err(EnqueueError::Full(requests)) => {
+            for (waiter, request) in waiters.into_iter().zip(requests) {
+                let e = SendRequestError::Full(request);
+                let _ = waiter.send(Ticket::Failed(BatchError::Send(e)));
+            }
+        }
+    }
+}
+
+/// Take the buffered batch, starting a new one
+fn take<Req, Resp>(buffer: &mut Buffer<Req, Resp>) -> Batch<Req, Resp> {
+    buffer.generation += 1;
+    Batch {
+        requests: std::mem::take(&mut buffer.batch.requests),
+        waiters: std::mem::take(&mut buffer.batch.waiters),
+    }
+}
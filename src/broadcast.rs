@@ -0,0 +1,58 @@
+//! Send one request to several independent [`Responder`](crate::Responder)s, and collect every response.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, broadcast::Broadcaster};
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (first, first_responder) = bounded::<&str, usize>(1);
+//! let (second, second_responder) = bounded::<&str, usize>(1);
+//! let broadcaster = Broadcaster::new(vec![first, second]);
+//! drop(second_responder);
+//! let (responses, _) = join!(broadcaster.broadcast("ping"), async {
+//!     first_responder.recv().await.unwrap().respond(1).unwrap()
+//! });
+//! assert!(matches!(responses[..], [Ok(1), Err(_)]))
+//! # })
+//! ```
+
+use crate::{Requester, SendRequestError};
+use futures::future::join_all;
+
+/// A set of [`Requester`]s, each to an independent [`Responder`](crate::Responder), which all receive every request
+pub struct Broadcaster<Req, Resp> {
+    backends: Vec<Requester<Req, Resp>>,
+}
+
+impl<Req, Resp> Broadcaster<Req, Resp> {
+    /// Create a broadcaster over `backends`
+    pub fn new(backends: impl IntoIterator<Item = Requester<Req, Resp>>) -> Self {
+        Self {
+            backends: backends.into_iter().collect(),
+        }
+    }
+
+    /// The [`Requester`]s in this broadcaster
+    pub fn backends(&self) -> &[Requester<Req, Resp>] {
+        &self.backends
+    }
+
+    /// Send a copy of `request` to every backend concurrently, and wait for all of their responses.
+    /// Responses are in the same order as the backends.
+    /// A backend which fails gives an error in its place, without affecting the others.
+    /// For a large request, use an [`Arc`](std::sync::Arc) as the `Req`, so that only the reference is cloned
+    pub async fn broadcast(&self, request: Req) -> Vec<Result<Resp, SendRequestError<Req>>>
+    where
+        Req: Clone,
+    {
+        let mut request = Some(request);
+        let last = self.backends.len().saturating_sub(1);
+        join_all(self.backends.iter().enumerate().map(|(index, backend)| {
+            let request = match index == last {
+                true => request.take().expect("only taken for the last backend"),
+                false => request.clone().expect("not taken yet"),
+            };
+            backend.send(request)
+        }))
+        .await
+    }
+}
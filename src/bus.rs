@@ -0,0 +1,112 @@
+//! Route requests to [`Responder`]s by subject, for a lightweight message bus.
+//!
+//! ```
+//! use bidirectional_channel::bus::Bus;
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let bus = Bus::<&str, usize>::bounded(1);
+//! let lengths = bus.responder("len");
+//! let (response, _) = join!(bus.request("len", "hello"), async {
+//!     let request = lengths.recv().await.unwrap();
+//!     let len = request.len();
+//!     request.respond(len).unwrap()
+//! });
+//! assert!(response.unwrap() == 5)
+//! # })
+//! ```
+
+use crate::{lock, Requester, Responder, SendRequestError};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// Error returned when making a request on a [`Bus`]
+#[derive(Error)]
+pub enum BusError<Req> {
+    /// No [`Responder`] is registered for the subject, or it was dropped.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("No Responder is registered for the subject")]
+    NoResponder(Req),
+    /// The registered [`Responder`] didn't respond
+    #[error(transparent)]
+    Send(#[from] SendRequestError<Req>),
+}
+impl<Req> Debug for BusError<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoResponder(_) => write!(f, "NoResponder(..)"),
+            Self::Send(e) => f.debug_tuple("Send").field(e).finish(),
+        }
+    }
+}
+
+/// A registry of request-response channels, keyed by subject.
+/// Clones share the same registry
+pub struct Bus<Req, Resp> {
+    capacity: usize,
+    subjects: Arc<Mutex<HashMap<String, Requester<Req, Resp>>>>,
+}
+
+impl<Req, Resp> Clone for Bus<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            subjects: self.subjects.clone(),
+        }
+    }
+}
+
+impl<Req, Resp> Bus<Req, Resp> {
+    /// Create a bus, where each subject's channel holds up to `capacity` requests.
+    /// See [`crate::bounded`]
+    pub fn bounded(capacity: usize) -> Self {
+        Self {
+            capacity,
+            subjects: Default::default(),
+        }
+    }
+
+    /// Register a [`Responder`] for `subject`.
+    /// Replaces any previous registration, whose [`Responder`] receives no new requests
+    pub fn responder(&self, subject: impl Into<String>) -> Responder<Req, Resp> {
+        let (requester, responder) = crate::bounded(self.capacity);
+        lock(&self.subjects).insert(subject.into(), requester);
+        responder
+    }
+
+    /// Make a request to the [`Responder`] registered for `subject`.
+    /// `await` the result to receive the response.
+    /// Fails with [`BusError::NoResponder`] if there isn't one, and returns your request back
+    pub async fn request(&self, subject: &str, request: Req) -> Result<Resp, BusError<Req>> {
+        let requester = lock(&self.subjects).get(subject).cloned();
+        let requester = match requester {
+            Some(requester) => requester,
+            None => return Err(BusError::NoResponder(request)),
+        };
+        match requester.send(request).await {
+            Err(SendRequestError::Closed(request)) => {
+                self.unregister_closed(subject);
+                Err(BusError::NoResponder(request))
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// The subjects with a registered [`Responder`]
+    pub fn subjects(&self) -> Vec<String> {
+        lock(&self.subjects).keys().cloned().collect()
+    }
+
+    fn unregister_closed(&self, subject: &str) {
+        let mut subjects = lock(&self.subjects);
+        // It may have been registered again in the meantime
+        if let Some(requester) = subjects.get(subject) {
+            if requester.is_closed() {
+                subjects.remove(subject);
+            }
+        }
+    }
+}
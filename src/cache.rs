@@ -0,0 +1,232 @@
+//! Answer repeated identical requests locally, without bothering the [`Responder`](crate::Responder).
+//!
+//! ```
+//! use bidirectional_channel::{bounded, cache::CachedRequester};
+//! use std::time::Duration;
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! let requester = CachedRequester::new(requester, Duration::from_secs(60));
+//! async_std::task::spawn(async move {
+//!     while let Ok(request) = responder.recv().await {
+//!         let len = request.len();
+//!         request.respond(len).unwrap();
+//!     }
+//! });
+//! assert!(requester.send("hello").await.unwrap() == 5);
+//! assert!(requester.send("hello").await.unwrap() == 5);
+//! assert!(requester.stats().hits == 1);
+//! # })
+//! ```
+
+use crate::{lock, Requester, SendRequestError};
+use futures::{
+    channel::oneshot,
+    future::{FutureExt, Shared},
+};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// How often a [`CachedRequester`] has answered from its cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from the cache, including those which waited on an identical request already in flight
+    pub hits: u64,
+    /// Requests sent to the [`Responder`](crate::Responder)
+    pub misses: u64,
+}
+
+/// A [`Requester`] which caches responses for `ttl`.
+/// Concurrent identical requests share a single send, and failures are never cached.
+/// Clones share the same cache
+#[derive(Clone)]
+pub struct CachedRequester<Req, Resp> {
+    inner: Requester<Req, Resp>,
+    ttl: Duration,
+    state: Arc<Mutex<State<Req, Resp>>>,
+}
+
+struct State<Req, Resp> {
+    entries: HashMap<Req, Entry<Resp>>,
+    stats: CacheStats,
+    /// When expired entries were last purged, so keys which are never requested again don't pile up
+    purged: Instant,
+}
+
+enum Entry<Resp> {
+    Fresh {
+        response: Resp,
+        expires: Instant,
+    },
+    /// Resolves when the request in flight is answered, or is cancelled if it failed
+    InFlight(Shared<oneshot::Receiver<Resp>>),
+}
+
+impl<Req, Resp> CachedRequester<Req, Resp>
+where
+    Req: Hash + Eq + Clone,
+    Resp: Clone,
+{
+    /// Cache responses from `inner` for `ttl`
+    pub fn new(inner: Requester<Req, Resp>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            state: Arc::new(Mutex::new(State {
+                entries: HashMap::new(),
+                stats: CacheStats::default(),
+                purged: Instant::now(),
+            })),
+        }
+    }
+
+    /// Make a request, answering from the cache if there's a fresh response.
+    /// `await` the result to receive the response.
+    /// Fails if the [`Responder`](crate::Responder) was dropped, or the request was ignored
+    pub async fn send(&self, request: Req) -> Result<Resp, SendRequestError<Req>> {
+        loop {
+            let in_flight = {
+                let mut state = lock(&self.state);
+                match state.entries.get(&request) {
+                    Some(Entry::Fresh { response, expires }) if *expires > Instant::now() => {
+                        let response = response.clone();
+                        state.stats.hits += 1;
+                        return Ok(response);
+                    }
+                    Some(Entry::InFlight(in_flight)) => Some(in_flight.clone()),
+                    _ => None,
+                }
+            };
+            match in_flight {
+                Some(in_flight) => match in_flight.await {
+                    Ok(response) => {
+                        lock(&self.state).stats.hits += 1;
+                        return Ok(response);
+                    }
+                    // That send failed - try for ourselves
+                    Err(oneshot::Canceled) => continue,
+                },
+                None => return self.send_inner(request).await,
+            }
+        }
+    }
+
+    /// Send `request` on to the [`Responder`](crate::Responder), sharing its response with any identical requests in the meantime
+    async fn send_inner(&self, request: Req) -> Result<Resp, SendRequestError<Req>> {
+        let (sender, receiver) = oneshot::channel();
+        let in_flight = receiver.shared();
+        {
+            let mut state = lock(&self.state);
+            state.stats.misses += 1;
+            state
+                .entries
+                .insert(request.clone(), Entry::InFlight(in_flight.clone()));
+        }
+        // Clear the entry if we fail, or are dropped
+        let guard = InFlightGuard {
+            cache: self,
+            request: Some(request.clone()),
+            in_flight,
+        };
+        let response = self.inner.send(request).await?;
+        let (request, in_flight) = guard.complete();
+        let now = Instant::now();
+        let mut state = lock(&self.state);
+        // Entries all expire within `ttl`, so purging that often keeps the cache to what was requested recently
+        if now >= state.purged + self.ttl {
+            state.entries.retain(
+                |_, entry| !matches!(entry, Entry::Fresh { expires, .. } if *expires <= now),
+            );
+            state.purged = now;
+        }
+        // Unless it was invalidated while in flight
+        if let Some(Entry::InFlight(ours)) = state.entries.get(&request) {
+            if ours.ptr_eq(&in_flight) {
+                state.entries.insert(
+                    request,
+                    Entry::Fresh {
+                        response: response.clone(),
+                        expires: now + self.ttl,
+                    },
+                );
+            }
+        }
+        drop(state);
+        let _ = sender.send(response.clone());
+        Ok(response)
+    }
+
+    /// How often this cache has been used
+    pub fn stats(&self) -> CacheStats {
+        lock(&self.state).stats
+    }
+
+    /// Forget the cached response for `request`, so the next one is sent to the [`Responder`](crate::Responder).
+    /// If `request` is in flight, its response isn't cached, though the requests already waiting on it still get it
+    pub fn invalidate(&self, request: &Req) {
+        lock(&self.state).entries.remove(request);
+    }
+
+    /// Forget every cached response, like [invalidating](CachedRequester::invalidate) each request
+    pub fn clear(&self) {
+        lock(&self.state).entries.clear()
+    }
+
+    /// How many requests are cached or in flight, including expired responses which haven't been purged yet
+    pub fn len(&self) -> usize {
+        lock(&self.state).entries.len()
+    }
+
+    /// Whether nothing is cached or in flight
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Recover the underlying [`Requester`]
+    pub fn into_inner(self) -> Requester<Req, Resp> {
+        self.inner
+    }
+}
+
+struct InFlightGuard<'a, Req, Resp>
+where
+    Req: Hash + Eq + Clone,
+    Resp: Clone,
+{
+    cache: &'a CachedRequester<Req, Resp>,
+    /// `None` once the send has succeeded
+    request: Option<Req>,
+    in_flight: Shared<oneshot::Receiver<Resp>>,
+}
+
+impl<'a, Req, Resp> InFlightGuard<'a, Req, Resp>
+where
+    Req: Hash + Eq + Clone,
+    Resp: Clone,
+{
+    fn complete(mut self) -> (Req, Shared<oneshot::Receiver<Resp>>) {
+        let request = self.request.take().expect("only completed once");
+        (request, self.in_flight.clone())
+    }
+}
+
+impl<'a, Req, Resp> Drop for InFlightGuard<'a, Req, Resp>
+where
+    Req: Hash + Eq + Clone,
+    Resp: Clone,
+{
+    fn drop(&mut self) {
+        if let Some(request) = self.request.take() {
+            let mut state = lock(&self.cache.state);
+            // Only remove our own entry - it may have been invalidated and replaced since
+            if let Some(Entry::InFlight(in_flight)) = state.entries.get(&request) {
+                if in_flight.ptr_eq(&self.in_flight) {
+                    state.entries.remove(&request);
+                }
+            }
+        }
+    }
+}
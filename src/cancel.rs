@@ -0,0 +1,99 @@
+use crate::{lock, EnqueueError, Requester, Response, SendRequestError};
+use futures::{future::poll_fn, task::AtomicWaker, FutureExt};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
+
+/// Cancels a request made with [`Requester::send_cancelable`].
+/// Clones cancel the same request
+pub struct CancelHandle<Resp> {
+    shared: Arc<Shared<Resp>>,
+}
+
+struct Shared<Resp> {
+    /// Taken on cancellation, so the [`Responder`](crate::Responder) can tell straight away
+    response: Mutex<Option<Response<Resp>>>,
+    /// Wakes the request future on cancellation
+    waker: AtomicWaker,
+}
+
+impl<Resp> Clone for CancelHandle<Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Resp> CancelHandle<Resp> {
+    /// Stop waiting for the response.
+    /// The request resolves with [`SendRequestError::Cancelled`],
+    /// and the [`UnRespondedRequest`](crate::UnRespondedRequest) sees [`is_canceled`](crate::UnRespondedRequest::is_canceled), so its `respond` fails.
+    /// Does nothing if the request has already resolved
+    pub fn cancel(&self) {
+        let response = lock(&self.shared.response).take();
+        drop(response);
+        self.shared.waker.wake();
+    }
+
+    /// Whether [`CancelHandle::cancel`] was called, or the request has resolved
+    pub fn is_canceled(&self) -> bool {
+        lock(&self.shared.response).is_none()
+    }
+}
+
+impl<Req, Resp, M: Default> Requester<Req, Resp, M> {
+    /// Make a request which can be cancelled, e.g. when the caller goes away.
+    /// `await` the future to receive the response, and use the [`CancelHandle`] to give up on it.
+    /// Cancelling while the channel is full drops the request without putting it on the channel.
+    /// Cancelling once the request is on the channel lets a [`Responder`](crate::Responder) which checks
+    /// [`UnRespondedRequest::is_canceled`](crate::UnRespondedRequest::is_canceled) stop work early.
+    pub fn send_cancelable(
+        &self,
+        request: Req,
+    ) -> (
+        CancelHandle<Resp>,
+        impl Future<Output = Result<Resp, SendRequestError<Req>>> + '_,
+    ) {
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        let shared = Arc::new(Shared {
+            response: Mutex::new(Some(Response::new(response_receiver))),
+            waker: AtomicWaker::new(),
+        });
+        let handle = CancelHandle {
+            shared: shared.clone(),
+        };
+        let response = async move {
+            let mut enqueue = self.outgoing.send(received);
+            // Cancelling while the channel is full drops the request, rather than enqueueing it later
+            poll_fn(|cx| {
+                shared.waker.register(cx.waker());
+                if lock(&shared.response).is_none() {
+                    return Poll::Ready(Err(SendRequestError::Cancelled));
+                }
+                enqueue
+                    .poll_unpin(cx)
+                    .map_err(|e| SendRequestError::from(EnqueueError::refused(e)))
+            })
+            .await?;
+            poll_fn(|cx| {
+                shared.waker.register(cx.waker());
+                let mut response = lock(&shared.response);
+                match response.as_mut() {
+                    None => Poll::Ready(Err(SendRequestError::Cancelled)),
+                    Some(pending) => match pending.poll_unpin(cx) {
+                        Poll::Ready(result) => {
+                            *response = None;
+                            Poll::Ready(result.map_err(SendRequestError::from))
+                        }
+                        Poll::Pending => Poll::Pending,
+                    },
+                }
+            })
+            .await
+        };
+        (handle, response)
+    }
+}
@@ -0,0 +1,150 @@
+//! A symmetric, full-duplex channel, where both ends send and receive.
+//!
+//! Unlike the request-response channel at the crate root, messages aren't paired up:
+//! each direction is an independent stream, and either end can send at any time.
+//! [`End::exchange`] sends and then waits for the next message from the other end, for ping-pong protocols.
+//!
+//! ```
+//! use bidirectional_channel::duplex;
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (client, server) = duplex::bounded::<&str, usize>(1);
+//! let (reply, ()) = join!(client.exchange("hello"), async {
+//!     let message = server.recv().await.unwrap();
+//!     server.send(message.len()).await.unwrap();
+//! });
+//! assert!(reply.unwrap() == 5);
+//! # })
+//! ```
+
+use crate::RecvError;
+use async_channel as channel;
+use futures::Stream;
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+
+/// Error returned by [`End::send`]
+#[derive(Error)]
+pub enum SendError<Out> {
+    /// Every [`End`] on the other side was dropped.
+    /// Returns ownership of the message that failed to send
+    #[error("The other end was dropped before the message was sent")]
+    Closed(Out),
+}
+impl<Out> Debug for SendError<Out> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+/// Error returned by [`End::exchange`]
+#[derive(Error)]
+pub enum ExchangeError<Out> {
+    /// Every [`End`] on the other side was dropped before the message was sent.
+    /// Returns ownership of the message
+    #[error("The other end was dropped before the message was sent")]
+    Closed(Out),
+    /// Every [`End`] on the other side was dropped after the message was sent, without sending anything back
+    #[error("The other end was dropped before replying")]
+    HungUp,
+}
+impl<Out> Debug for ExchangeError<Out> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "Closed(..)"),
+            Self::HungUp => write!(f, "HungUp"),
+        }
+    }
+}
+
+/// One end of a [`duplex`](self) channel, which sends `Out` and receives `In`.
+/// Clones share the same streams, so each incoming message is received by only one of them.
+/// This is also a [`Stream`] of incoming messages
+pub struct End<Out, In> {
+    us: channel::Sender<Out>,
+    them: channel::Receiver<In>,
+}
+
+impl<Out, In> Clone for End<Out, In> {
+    fn clone(&self) -> Self {
+        Self {
+            us: self.us.clone(),
+            them: self.them.clone(),
+        }
+    }
+}
+
+impl<Out, In> End<Out, In> {
+    /// Send a message to the other end, waiting while its buffer is full
+    pub async fn send(&self, message: Out) -> Result<(), SendError<Out>> {
+        self.us
+            .send(message)
+            .await
+            .map_err(|e| SendError::Closed(e.into_inner()))
+    }
+
+    /// Receive the next message from the other end.
+    /// Fails once every [`End`] on the other side was dropped, and nothing is left to receive
+    pub async fn recv(&self) -> Result<In, RecvError> {
+        self.them.recv().await.map_err(|_| RecvError)
+    }
+
+    /// Send a message, then wait for the next message from the other end.
+    /// Messages are not paired up, so the reply is just whatever the other end sends next
+    pub async fn exchange(&self, message: Out) -> Result<In, ExchangeError<Out>> {
+        self.us
+            .send(message)
+            .await
+            .map_err(|e| ExchangeError::Closed(e.into_inner()))?;
+        self.them.recv().await.map_err(|_| ExchangeError::HungUp)
+    }
+}
+
+impl<Out, In> Stream for End<Out, In> {
+    type Item = In;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<In>> {
+        Pin::new(&mut self.them).poll_next(cx)
+    }
+}
+
+/// Create a pair of [`End`]s, where each direction buffers up to `capacity` messages.
+///
+/// # Panics
+/// If `capacity` is zero
+pub fn bounded<A, B>(capacity: usize) -> (End<A, B>, End<B, A>) {
+    let (a, from_a) = channel::bounded(capacity);
+    let (b, from_b) = channel::bounded(capacity);
+    (
+        End {
+            us: a,
+            them: from_b,
+        },
+        End {
+            us: b,
+            them: from_a,
+        },
+    )
+}
+
+/// Create a pair of [`End`]s, where each direction buffers any number of messages
+pub fn unbounded<A, B>() -> (End<A, B>, End<B, A>) {
+    let (a, from_a) = channel::unbounded();
+    let (b, from_b) = channel::unbounded();
+    (
+        End {
+            us: a,
+            them: from_b,
+        },
+        End {
+            us: b,
+            them: from_a,
+        },
+    )
+}
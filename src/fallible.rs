@@ -0,0 +1,76 @@
+use crate::{EnqueueError, ReceivedRequest, Requester, ResponseError};
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// Error returned by [`Requester::send_fallible`].
+/// Distinguishes the handler refusing the request from the channel breaking
+#[derive(Error)]
+pub enum RequestError<Req, E> {
+    /// The [`Responder`](crate::Responder) for this channel was dropped.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The Responder was dropped before the message was sent")]
+    Closed(Req),
+    /// The [`UnRespondedRequest`](crate::UnRespondedRequest) for this request was dropped.
+    #[error("The UnRespondedRequest was dropped, not responded to")]
+    Ignored,
+    /// The request was evicted from the full channel by [`Requester::send_overwrite`].
+    #[error("The request was shed to make space on the channel")]
+    Shed,
+    /// The channel was full, and its [`Overflow`](crate::Overflow) policy is [`Overflow::Reject`](crate::Overflow::Reject).
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The channel was full")]
+    Full(Req),
+    /// The handler responded with an error
+    #[error("The handler failed")]
+    Handler(E),
+}
+impl<Req, E: Debug> Debug for RequestError<Req, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "Closed(..)"),
+            Self::Ignored => write!(f, "Ignored"),
+            Self::Shed => write!(f, "Shed"),
+            Self::Full(_) => write!(f, "Full(..)"),
+            Self::Handler(e) => f.debug_tuple("Handler").field(e).finish(),
+        }
+    }
+}
+impl<Req, E> From<EnqueueError<Req>> for RequestError<Req, E> {
+    fn from(e: EnqueueError<Req>) -> Self {
+        match e {
+            EnqueueError::Closed(request) => Self::Closed(request),
+            EnqueueError::Full(request) => Self::Full(request),
+        }
+    }
+}
+impl<Req, E> From<ResponseError> for RequestError<Req, E> {
+    fn from(e: ResponseError) -> Self {
+        match e {
+            ResponseError::Ignored => Self::Ignored,
+            ResponseError::Shed => Self::Shed,
+        }
+    }
+}
+
+impl<Req, T, E> Requester<Req, Result<T, E>> {
+    /// Make a request to a handler which can fail.
+    /// `await` the result to receive the successful response.
+    /// Fails with [`RequestError::Handler`] if the handler responded with an error,
+    /// or like [`Requester::send`] if the channel broke
+    pub async fn send_fallible(&self, request: Req) -> Result<T, RequestError<Req, E>> {
+        // Without a timeout or cancellation, so every way it can fail has a `RequestError`
+        let response = self.request(request).await?;
+        match response.await? {
+            Ok(response) => Ok(response),
+            Err(e) => Err(RequestError::Handler(e)),
+        }
+    }
+}
+
+impl<Req, T, E> ReceivedRequest<Req, Result<T, E>> {
+    /// Respond to the [`Requester`]'s request with an error, and take ownership of it.
+    /// Shorthand for `respond(Err(e))`
+    pub fn respond_err(self, e: E) -> Result<Req, (Req, Result<T, E>)> {
+        self.respond(Err(e))
+    }
+}
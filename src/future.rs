@@ -0,0 +1,147 @@
+use crate::{outgoing::Enqueue, EnqueueError, Reply, ResponseError, SendRequestError};
+use futures::channel::oneshot;
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Future returned by [`Requester::send`](crate::Requester::send) and [`Requester::send_before`](crate::Requester::send_before).
+/// Resolves to the response, once the request has been put on the channel and responded to.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendFuture<'a, Req, Resp, M = ()> {
+    state: State<'a, Req, Resp, M>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    /// Whether the channel was full at some point
+    #[cfg(feature = "tracing")]
+    blocked: bool,
+}
+
+// Boxing the request would cost an allocation per send, just to shrink the finished states
+#[allow(clippy::large_enum_variant)]
+enum State<'a, Req, Resp, M> {
+    Enqueueing(Enqueue<'a, Req, Resp, M>, oneshot::Receiver<Reply<Resp>>),
+    Waiting(oneshot::Receiver<Reply<Resp>>),
+    Done,
+}
+
+impl<'a, Req, Resp, M> SendFuture<'a, Req, Resp, M> {
+    pub(crate) fn new(
+        send: Enqueue<'a, Req, Resp, M>,
+        response_receiver: oneshot::Receiver<Reply<Resp>>,
+    ) -> Self {
+        Self {
+            state: State::Enqueueing(send, response_receiver),
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
+            #[cfg(feature = "tracing")]
+            blocked: false,
+        }
+    }
+
+    /// Poll inside `span`, recording whether the channel was full
+    #[cfg(feature = "tracing")]
+    pub(crate) fn instrument(mut self, span: tracing::Span) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+impl<'a, Req, Resp, M> Future for SendFuture<'a, Req, Resp, M> {
+    type Output = Result<Resp, SendRequestError<Req>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        #[cfg(feature = "tracing")]
+        let span = self.span.clone();
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        loop {
+            match &mut self.state {
+                State::Enqueueing(send, _) => match Pin::new(send).poll(cx) {
+                    Poll::Ready(Ok(())) => match mem::replace(&mut self.state, State::Done) {
+                        State::Enqueueing(_, response_receiver) => {
+                            #[cfg(feature = "tracing")]
+                            self.span.record("blocked", self.blocked);
+                            self.state = State::Waiting(response_receiver)
+                        }
+                        _ => unreachable!(),
+                    },
+                    Poll::Ready(Err(e)) => {
+                        self.state = State::Done;
+                        return Poll::Ready(Err(EnqueueError::refused(e).into()));
+                    }
+                    Poll::Pending => {
+                        #[cfg(feature = "tracing")]
+                        {
+                            self.blocked = true;
+                        }
+                        return Poll::Pending;
+                    }
+                },
+                State::Waiting(response_receiver) => {
+                    let response = futures::ready!(Pin::new(response_receiver).poll(cx));
+                    self.state = State::Done;
+                    return Poll::Ready(
+                        response
+                            .map_err(|_| ResponseError::Ignored)
+                            .and_then(Reply::into_response)
+                            .map_err(SendRequestError::from),
+                    );
+                }
+                State::Done => panic!("SendFuture polled after completion"),
+            }
+        }
+    }
+}
+
+/// Future returned by [`Requester::send_and_confirm`](crate::Requester::send_and_confirm).
+/// Puts the request on the channel, and resolves as soon as the channel accepts it.
+/// Fails with [`EnqueueError::Closed`] if the [`Responder`](crate::Responder) was dropped, and returns your request back
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct EnqueuedConfirmation<'a, Req, Resp, M = ()> {
+    send: Enqueue<'a, Req, Resp, M>,
+}
+
+impl<'a, Req, Resp, M> EnqueuedConfirmation<'a, Req, Resp, M> {
+    pub(crate) fn new(send: Enqueue<'a, Req, Resp, M>) -> Self {
+        Self { send }
+    }
+}
+
+impl<'a, Req, Resp, M> Future for EnqueuedConfirmation<'a, Req, Resp, M> {
+    type Output = Result<(), EnqueueError<Req>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.send)
+            .poll(cx)
+            .map_err(EnqueueError::refused)
+    }
+}
+
+/// Future which resolves to the response, for a request which is already on the channel.
+/// Returned by [`Requester::request`](crate::Requester::request) and [`Requester::try_send`](crate::Requester::try_send).
+/// Fails with [`ResponseError::Ignored`] if the [`UnRespondedRequest`](crate::UnRespondedRequest) was dropped
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Response<Resp> {
+    response_receiver: oneshot::Receiver<Reply<Resp>>,
+}
+
+impl<Resp> Response<Resp> {
+    pub(crate) fn new(response_receiver: oneshot::Receiver<Reply<Resp>>) -> Self {
+        Self { response_receiver }
+    }
+}
+
+impl<Resp> Future for Response<Resp> {
+    type Output = Result<Resp, ResponseError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.response_receiver).poll(cx).map(|reply| {
+            reply
+                .map_err(|_| ResponseError::Ignored)
+                .and_then(Reply::into_response)
+        })
+    }
+}
@@ -0,0 +1,208 @@
+//! Responder-side deduplication of retried requests, for exactly-once handling.
+//!
+//! A [`Requester`](crate::Requester) tags each request with a token using [`Requester::send_idempotent`](crate::Requester::send_idempotent),
+//! reusing the token when it retries - even after a restart.
+//! An [`Idempotent`] responder remembers the response for each token,
+//! and answers duplicates itself instead of yielding them to the handler again.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, idempotency::IdempotencyConfig, ResponderExt};
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! let responder = responder.idempotent(IdempotencyConfig::default());
+//! async_std::task::spawn(async move {
+//!     while let Ok(request) = responder.recv().await {
+//!         let len = request.len();
+//!         request.respond(len).unwrap();
+//!     }
+//! });
+//! assert!(requester.send_idempotent(1, "hello").await.unwrap() == 5);
+//! // The handler doesn't see this one
+//! assert!(requester.send_idempotent(1, "hello").await.unwrap() == 5);
+//! # })
+//! ```
+
+use crate::{lock, ReceivedRequest, RecvError, Responder, UnRespondedRequest};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// How long, and how many, responses an [`Idempotent`] responder remembers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdempotencyConfig {
+    /// The most responses to remember - the oldest are evicted first
+    pub capacity: usize,
+    /// How long to remember each response for
+    pub ttl: Duration,
+}
+
+impl Default for IdempotencyConfig {
+    /// Remember up to 1024 responses, for a minute each
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A [`Responder`] which answers requests with a repeated idempotency token from its cache.
+/// Returned by [`ResponderExt::idempotent`](crate::ResponderExt::idempotent)
+pub struct Idempotent<Req, Resp, M = ()> {
+    responder: Responder<Req, Resp, M>,
+    cache: Arc<Mutex<Cache<Resp, M>>>,
+}
+
+struct Cache<Resp, M> {
+    config: IdempotencyConfig,
+    entries: HashMap<u128, Entry<Resp, M>>,
+    /// Tokens of responded entries, oldest first
+    order: VecDeque<(u128, Instant)>,
+}
+
+enum Entry<Resp, M> {
+    /// The handler is working on it - duplicates wait here for its response
+    InFlight(Vec<UnRespondedRequest<Resp, M>>),
+    Responded {
+        response: Resp,
+        at: Instant,
+    },
+}
+
+impl<Resp, M> Cache<Resp, M> {
+    fn purge(&mut self) {
+        let now = Instant::now();
+        while let Some((token, at)) = self.order.front().copied() {
+            let expired = now.duration_since(at) >= self.config.ttl;
+            if !expired && self.order.len() <= self.config.capacity {
+                break;
+            }
+            self.order.pop_front();
+            // The token may have been forgotten, and used again since
+            if let Some(Entry::Responded { at: current, .. }) = self.entries.get(&token) {
+                if *current == at {
+                    self.entries.remove(&token);
+                }
+            }
+        }
+    }
+}
+
+impl<Req, Resp, M> Idempotent<Req, Resp, M>
+where
+    Resp: Clone + Send + 'static,
+    M: Send + 'static,
+{
+    pub(crate) fn new(responder: Responder<Req, Resp, M>, config: IdempotencyConfig) -> Self {
+        Self {
+            responder,
+            cache: Arc::new(Mutex::new(Cache {
+                config,
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Receive the next request which needs handling.
+    /// Requests with a token that has already been responded to are answered from the cache in the meantime.
+    /// Fails if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    pub async fn recv(&self) -> Result<ReceivedRequest<Req, Resp, M>, RecvError> {
+        loop {
+            let mut received = self.responder.recv().await?;
+            let token = match received.idempotency_token() {
+                Some(token) => token,
+                None => return Ok(received),
+            };
+            let mut cache = lock(&self.cache);
+            cache.purge();
+            match cache.entries.get_mut(&token) {
+                Some(Entry::Responded { response, .. }) => {
+                    let response = response.clone();
+                    drop(cache);
+                    let _ = received.respond(response);
+                }
+                Some(Entry::InFlight(duplicates)) => duplicates.push(received.unresponded),
+                None => {
+                    cache.entries.insert(token, Entry::InFlight(Vec::new()));
+                    drop(cache);
+                    let on_respond = Arc::clone(&self.cache);
+                    received.unresponded.on_respond = Some(Box::new(move |response: &Resp| {
+                        remember(&on_respond, token, response)
+                    }));
+                    received.unresponded.trackers.push(Box::new(InFlightGuard {
+                        cache: Arc::clone(&self.cache),
+                        token,
+                    }));
+                    return Ok(received);
+                }
+            }
+        }
+    }
+
+    /// How many responses are currently remembered
+    pub fn len(&self) -> usize {
+        lock(&self.cache).order.len()
+    }
+
+    /// Whether no responses are currently remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every remembered response
+    pub fn clear(&self) {
+        let mut cache = lock(&self.cache);
+        cache.order.clear();
+        cache
+            .entries
+            .retain(|_, entry| matches!(entry, Entry::InFlight(_)));
+    }
+
+    /// Recover the underlying [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp, M> {
+        self.responder
+    }
+}
+
+/// Cache the handler's response, and pass it on to any duplicates which arrived while it was working
+fn remember<Resp: Clone, M>(cache: &Mutex<Cache<Resp, M>>, token: u128, response: &Resp) {
+    let mut cache = lock(cache);
+    let at = Instant::now();
+    let duplicates = match cache.entries.insert(
+        token,
+        Entry::Responded {
+            response: response.clone(),
+            at,
+        },
+    ) {
+        Some(Entry::InFlight(duplicates)) => duplicates,
+        _ => Vec::new(),
+    };
+    cache.order.push_back((token, at));
+    cache.purge();
+    drop(cache);
+    for duplicate in duplicates {
+        let _ = duplicate.respond(response.clone());
+    }
+}
+
+/// Forgets the token if the handler drops the request without responding, so a retry is handled afresh
+struct InFlightGuard<Resp, M> {
+    cache: Arc<Mutex<Cache<Resp, M>>>,
+    token: u128,
+}
+
+impl<Resp, M> Drop for InFlightGuard<Resp, M> {
+    fn drop(&mut self) {
+        let mut cache = lock(&self.cache);
+        if let Some(Entry::InFlight(_)) = cache.entries.get(&self.token) {
+            // Dropping the duplicates lets their requesters know they were ignored
+            let abandoned = cache.entries.remove(&self.token);
+            drop(cache);
+            drop(abandoned);
+        }
+    }
+}
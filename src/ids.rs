@@ -0,0 +1,78 @@
+use crate::{Requester, Responder, SendFuture, SendRequestError};
+use derive_more::Display;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// Identifies a request on a channel created by [`bounded_with_ids`] or [`unbounded_with_ids`].
+/// Ids count up from zero, and are unique per channel
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct RequestId(pub u64);
+
+/// A [`Requester`] which assigns a [`RequestId`] to each request, for correlating requests and responses (e.g. in logs).
+/// The [`Responder`] reads it with [`ReceivedRequest::id`](crate::ReceivedRequest::id).
+/// Clones share the same sequence of ids
+pub struct IdRequester<Req, Resp> {
+    inner: Requester<Req, Resp>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl<Req, Resp> Clone for IdRequester<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+}
+
+impl<Req, Resp> IdRequester<Req, Resp> {
+    /// Make a request.
+    /// `await` the result to receive the response, alongside the id it was sent with.
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub async fn send(&self, request: Req) -> Result<(RequestId, Resp), SendRequestError<Req>> {
+        let id = RequestId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (received, response_receiver) =
+            self.inner
+                .prepare_counted(request, (), |unresponded| unresponded.id = Some(id));
+        let response =
+            SendFuture::new(self.inner.outgoing.send(received), response_receiver).await?;
+        Ok((id, response))
+    }
+
+    /// Recover the underlying [`Requester`], whose requests don't have ids
+    pub fn into_inner(self) -> Requester<Req, Resp> {
+        self.inner
+    }
+}
+
+/// Create a bounded [`IdRequester`]-[`Responder`] pair.
+/// See [`crate::bounded`]
+pub fn bounded_with_ids<Req, Resp>(
+    capacity: usize,
+) -> (IdRequester<Req, Resp>, Responder<Req, Resp>) {
+    let (inner, responder) = crate::bounded(capacity);
+    (
+        IdRequester {
+            inner,
+            next_id: Default::default(),
+        },
+        responder,
+    )
+}
+
+/// Create an unbounded [`IdRequester`]-[`Responder`] pair.
+/// See [`crate::unbounded`]
+pub fn unbounded_with_ids<Req, Resp>() -> (IdRequester<Req, Resp>, Responder<Req, Resp>) {
+    let (inner, responder) = crate::unbounded();
+    (
+        IdRequester {
+            inner,
+            next_id: Default::default(),
+        },
+        responder,
+    )
+}
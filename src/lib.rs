@@ -19,19 +19,136 @@
 //! assert!(request.len() == response)
 //! # })
 //! ```
+//!
+//! To hand the response off to another task while keeping the request, [split](ReceivedRequest::split) it from its handle:
+//!
+//! ```
+//! # use futures::join;
+//! # async_std::task::block_on( async {
+//! use bidirectional_channel::{bounded};
+//! let (requester, responder) = bounded::<String, usize>(1);
+//! let responder = async {
+//!     let (request, unresponded) = responder.recv().await.unwrap().split();
+//!     let len = request.len();
+//!     async_std::task::spawn(async move { unresponded.respond(len).unwrap() }).await;
+//!     request
+//! };
+//! let (response, request) = join!(requester.send(String::from("hello")), responder);
+//! assert!(request.len() == response.unwrap())
+//! # })
+//! ```
+//!
+//! Responses are moved to the requester, never cloned.
+//! To share one large response between many requests without copying it, make the response type cheap to clone,
+//! like an [`Arc`] (or `bytes::Bytes`), and respond with a handle to the shared buffer:
+//!
+//! ```
+//! # use futures::join;
+//! # async_std::task::block_on( async {
+//! use bidirectional_channel::{bounded};
+//! use std::sync::Arc;
+//! let buffer: Arc<[u8]> = Arc::from(vec![0; 1024]);
+//! let (requester, responder) = bounded::<(), Arc<[u8]>>(1);
+//! let responder = async {
+//!     let request = responder.recv().await.unwrap();
+//!     request.respond(Arc::clone(&buffer)).unwrap()
+//! };
+//! let (response, _) = join!(requester.send(()), responder);
+//! assert!(Arc::ptr_eq(&response.unwrap(), &buffer))
+//! # })
+//! ```
+//!
+//! The channel itself works on any runtime.
+//! Runtime-specific helpers are behind features:
+//! - `async-std` (default): spawning onto async-std, in `spawn::AsyncStd`
+//! - `tokio`: spawning onto tokio, in `spawn::Tokio`
+//! - `sync`: a blocking facade, in `sync`
+//! - `serde`: serializing `RequestId`s and `ResponseTicket`s, to pass requests between processes
+//! - `tower`: serving requests with a `tower::Service`, in `service`
+//! - `blocking`: blocking methods on the async handles, like `Requester::send_blocking`
+//! - `tracing`: a span around `Requester::send`, and events when the request is received and responded to
+//! - `flume`, `crossbeam`: queueing requests on flume or crossbeam-channel instead of async-channel, see `backend`
 
-use async_std::channel;
-/// An [`async_std::channel::Receiver`] which receives an [`UnRespondedRequest<Req, Resp>`] instead of a `Req`.
-pub use async_std::channel::Receiver as Responder;
+use async_channel as channel;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
-use futures::channel::oneshot;
-use std::fmt::Debug;
+use event_listener::{Event, EventListener};
+use futures::{
+    channel::oneshot,
+    future::{select, Either, FutureExt, Shared},
+};
+use futures_timer::Delay;
 #[cfg(doc)]
 use std::ops::{Deref, DerefMut};
+use std::{
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, PoisonError, Weak,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
+mod cancel;
+pub use cancel::CancelHandle;
+mod fallible;
+pub use fallible::RequestError;
+mod future;
+pub use future::{EnqueuedConfirmation, Response, SendFuture};
+mod ids;
+pub use ids::{bounded_with_ids, unbounded_with_ids, IdRequester, RequestId};
+mod macros;
+mod mapped;
+pub use mapped::MappedRequester;
+mod metrics;
+use metrics::{MetricsSink, Recorder};
+pub use metrics::{Outcome, RequestMetrics};
+mod owed;
+pub use owed::{OwedResponse, OwedResponses, OwedStream};
+mod pending;
+pub use pending::PendingResponses;
+mod responder;
+pub use responder::{
+    select_any, Coalesce, MapRequest, Recv, RecvStamped, Responder, ResponderExt, Throttled,
+    Validated,
+};
+mod responses;
+pub use responses::Responses;
+mod serial;
+pub use serial::{SerialRequest, SerialResponder};
+mod outgoing;
+pub use outgoing::Overflow;
+use outgoing::{Limit, Outgoing, Queue, Slot};
+mod sink;
+pub use sink::RequestSink;
+mod ticket;
+pub use ticket::{ResponseTicket, Tickets};
+mod timeout;
+pub use timeout::{bounded_with_timeout, unbounded_with_timeout, TimeoutRequester};
+pub mod actor;
+pub mod backend;
+use backend::ChannelBackend;
+pub mod batching;
+pub mod broadcast;
+pub mod bus;
+pub mod cache;
+pub mod duplex;
+pub mod idempotency;
+pub mod pool;
+pub mod priority;
+pub mod returning;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod spawn;
+pub mod streaming;
+#[cfg(feature = "sync")]
+pub mod sync;
+
 /// Error returned when sending a request
-#[derive(Error)]
+#[derive(Error, Clone, PartialEq, Eq)]
 pub enum SendRequestError<Req> {
     /// The [`Responder`] for this channel was dropped.
     /// Returns ownership of the `Req` that failed to send
@@ -40,36 +157,547 @@ pub enum SendRequestError<Req> {
     /// The [`UnRespondedRequest`] for this request was dropped.
     #[error("The UnRespondedRequest was dropped, not responded to")]
     Ignored,
+    /// The timeout expired before the request could be put on the channel.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("Timed out before the message was sent")]
+    TimedOut(Req),
+    /// The timeout expired after the request was put on the channel, but before it was responded to.
+    #[error("Timed out waiting for the response")]
+    ResponseTimedOut,
+    /// The request was cancelled with a [`CancelHandle`].
+    #[error("The request was cancelled")]
+    Cancelled,
+    /// The request was evicted from the full channel by [`Requester::send_overwrite`], before the [`Responder`] received it.
+    #[error("The request was shed to make space on the channel")]
+    Shed,
+    /// The channel was full, and its [`Overflow`] policy is [`Overflow::Reject`].
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The channel was full")]
+    Full(Req),
 }
 impl<Req> Debug for SendRequestError<Req> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Closed(_) => write!(f, "Closed(..)"),
-            Self::Ignored => write!(f, "Cancelled"),
+            Self::Ignored => write!(f, "Ignored"),
+            Self::TimedOut(_) => write!(f, "TimedOut(..)"),
+            Self::ResponseTimedOut => write!(f, "ResponseTimedOut"),
+            Self::Cancelled => write!(f, "Cancelled"),
+            Self::Shed => write!(f, "Shed"),
+            Self::Full(_) => write!(f, "Full(..)"),
+        }
+    }
+}
+impl<Req> SendRequestError<Req> {
+    /// Take back the request, if it never reached the channel (e.g. to retry it on another one).
+    /// Returns `Some` for [`SendRequestError::Closed`], [`SendRequestError::TimedOut`] and [`SendRequestError::Full`]
+    pub fn into_inner(self) -> Option<Req> {
+        match self {
+            Self::Closed(request) | Self::TimedOut(request) | Self::Full(request) => Some(request),
+            Self::Ignored | Self::ResponseTimedOut | Self::Cancelled | Self::Shed => None,
         }
     }
+
+    /// Whether this is [`SendRequestError::Closed`]
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed(_))
+    }
+
+    /// Whether this is [`SendRequestError::Ignored`]
+    pub fn is_ignored(&self) -> bool {
+        matches!(self, Self::Ignored)
+    }
+}
+
+/// Error returned when receiving a request, because every [`Requester`] was dropped, and the channel is empty
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The channel is closed and empty")]
+pub struct RecvError;
+
+/// Error returned by [`Responder::try_recv`]
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// There are no requests on the channel
+    #[error("The channel is empty")]
+    Empty,
+    /// Every [`Requester`] was dropped, and the channel is empty
+    #[error("The channel is closed and empty")]
+    Closed,
 }
 
+/// Error returned when putting a request on the channel, without waiting for a response
+#[derive(Error, Clone, PartialEq, Eq)]
+pub enum EnqueueError<Req> {
+    /// The [`Responder`] for this channel was dropped.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The Responder was dropped before the message was sent")]
+    Closed(Req),
+    /// The channel was full, and its [`Overflow`] policy is [`Overflow::Reject`].
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The channel was full")]
+    Full(Req),
+}
+impl<Req> Debug for EnqueueError<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "Closed(..)"),
+            Self::Full(_) => write!(f, "Full(..)"),
+        }
+    }
+}
+impl<Req> EnqueueError<Req> {
+    /// Take back the request which failed to send
+    pub fn into_inner(self) -> Req {
+        match self {
+            Self::Closed(request) | Self::Full(request) => request,
+        }
+    }
+
+    /// Why the channel gave the request back
+    fn refused<Resp, M>(e: channel::TrySendError<ReceivedRequest<Req, Resp, M>>) -> Self {
+        match e {
+            channel::TrySendError::Closed(received) => Self::Closed(received.into_refused()),
+            channel::TrySendError::Full(received) => Self::Full(received.into_refused()),
+        }
+    }
+}
+impl<Req> From<EnqueueError<Req>> for SendRequestError<Req> {
+    fn from(e: EnqueueError<Req>) -> Self {
+        match e {
+            EnqueueError::Closed(request) => Self::Closed(request),
+            EnqueueError::Full(request) => Self::Full(request),
+        }
+    }
+}
+
+/// Error returned by [`Requester::try_send`]
+#[derive(Error, Clone, PartialEq, Eq)]
+pub enum TrySendError<Req> {
+    /// The channel is full.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The channel is full")]
+    Full(Req),
+    /// The [`Responder`] for this channel was dropped.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The Responder was dropped before the message was sent")]
+    Closed(Req),
+}
+impl<Req> Debug for TrySendError<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(_) => write!(f, "Full(..)"),
+            Self::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+/// Error returned when waiting for a response, once the request is on the channel
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseError {
+    /// The [`UnRespondedRequest`] for this request was dropped.
+    #[error("The UnRespondedRequest was dropped, not responded to")]
+    Ignored,
+    /// The request was evicted from the full channel by [`Requester::send_overwrite`], before the [`Responder`] received it.
+    #[error("The request was shed to make space on the channel")]
+    Shed,
+}
+impl<Req> From<ResponseError> for SendRequestError<Req> {
+    fn from(e: ResponseError) -> Self {
+        match e {
+            ResponseError::Ignored => Self::Ignored,
+            ResponseError::Shed => Self::Shed,
+        }
+    }
+}
+/// For funnelling channel errors into an [`io::Result`](std::io::Result).
+/// Any returned `Req` is dropped
+impl<Req> From<SendRequestError<Req>> for std::io::Error {
+    fn from(e: SendRequestError<Req>) -> Self {
+        use std::io::ErrorKind;
+        let kind = match e {
+            SendRequestError::Closed(_) => ErrorKind::BrokenPipe,
+            SendRequestError::Ignored => ErrorKind::ConnectionReset,
+            SendRequestError::TimedOut(_) | SendRequestError::ResponseTimedOut => {
+                ErrorKind::TimedOut
+            }
+            SendRequestError::Full(_) => ErrorKind::WouldBlock,
+            SendRequestError::Cancelled | SendRequestError::Shed => ErrorKind::Other,
+        };
+        Self::new(kind, e.to_string())
+    }
+}
+
+type OnRespond<Resp> = Box<dyn FnOnce(&Resp) + Send + Sync>;
+
 /// Represents that the [`Requester`] associated with this communication is still waiting for a response.
+/// This is `Send + 'static` whenever `Resp` is, so it can be handed to another task (e.g. with `async_std::task::spawn` or `tokio::spawn`),
+/// and responded to from there, while the receiving task carries on.
 #[must_use = "You must respond to the request"]
-pub struct UnRespondedRequest<Resp> {
-    response_sender: oneshot::Sender<Resp>,
+pub struct UnRespondedRequest<Resp, M = ()> {
+    /// Taken when this is responded to
+    response_sender: Option<oneshot::Sender<Reply<Resp>>>,
+    /// Set by [`ResponderExt::recv_stamped`]
+    received_at: Option<Instant>,
+    /// Set by [`Requester::send_before`] and [`Requester::send_deadline`]
+    deadline: Option<Instant>,
+    /// Set when the [`Responder`] receives the request
+    queue_depth: usize,
+    /// Set by [`Requester::send_deadline`], so that late responses fail
+    expires: bool,
+    /// Set by [`Requester::notify`], so that responding does nothing
+    notification: bool,
+    /// Set by [`Requester::send_idempotent`]
+    idempotency_token: Option<u128>,
+    /// Set by an [`IdRequester`]
+    id: Option<RequestId>,
+    /// Set by [`Requester::send_with`], or `M::default()` otherwise
+    meta: M,
+    /// Set on a [`rendezvous`] channel, and fired once the [`Responder`] receives the request
+    picked_up: Option<oneshot::Sender<()>>,
+    /// Holds the request's place on the channel, until the [`Responder`] receives it
+    slot: Option<Slot>,
+    /// Called with the response, just before it's sent
+    on_respond: Option<OnRespond<Resp>>,
+    /// Dropped when this is responded to, or dropped
+    trackers: Vec<Box<dyn Send + Sync>>,
+    /// Set by [`Requester::with_metrics`]
+    metrics: Option<Recorder>,
+    /// The [`Requester`]'s span when the request was made
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+impl<Resp, M> Debug for UnRespondedRequest<Resp, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnRespondedRequest")
+            .field("requester_waiting", &!self.is_canceled())
+            .field("id", &self.id)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
 }
-impl<Resp> UnRespondedRequest<Resp> {
+
+impl<Resp, M> UnRespondedRequest<Resp, M> {
+    fn new(response_sender: Option<oneshot::Sender<Reply<Resp>>>, meta: M) -> Self {
+        Self {
+            response_sender,
+            received_at: None,
+            deadline: None,
+            queue_depth: 0,
+            expires: false,
+            notification: false,
+            idempotency_token: None,
+            id: None,
+            meta,
+            picked_up: None,
+            slot: None,
+            on_respond: None,
+            trackers: Vec::new(),
+            metrics: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
+    }
+
+    /// The deadline the [`Requester`] attached with [`Requester::send_before`] or [`Requester::send_deadline`], if any
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// How many requests were still waiting on the channel behind this one when the [`Responder`] received it,
+    /// e.g. to switch to a cheaper way of handling requests under load.
+    /// This is a snapshot, so it may be stale straight away
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth
+    }
+
+    /// Whether the deadline has passed, so it may not be worth doing the work.
+    /// This is `false` if there is no deadline
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// How much time is left before the deadline, if there is one.
+    /// This is zero once the deadline has passed
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
     /// Respond to the [`Requester`]'s request.
-    /// Fails if the associated [`Requester`] was dropped, and returns your response back
-    pub fn respond(self, response: Resp) -> Result<(), Resp> {
-        self.response_sender.send(response)
+    /// Fails, and returns your response back, if:
+    /// - the [`Requester`] stopped waiting for the response, because it was dropped, timed out,
+    ///   or the request was cancelled with a [`CancelHandle`] (see [`UnRespondedRequest::is_canceled`])
+    /// - the request was sent with [`Requester::send_deadline`], and the deadline has passed
+    pub fn respond(mut self, response: Resp) -> Result<(), Resp> {
+        self.try_respond(response)
+    }
+
+    /// Respond to the [`Requester`]'s request without giving up the handle, e.g. to keep reading its metadata.
+    /// Once this succeeds, the handle is spent, so responding again fails.
+    ///
+    /// Fails, and returns your response back, if:
+    /// - the [`Requester`] stopped waiting for the response, because it was dropped, timed out,
+    ///   or the request was cancelled with a [`CancelHandle`] (see [`UnRespondedRequest::is_canceled`])
+    /// - the request was sent with [`Requester::send_deadline`], and the deadline has passed
+    /// - the handle is already spent
+    ///
+    /// None of the failures are worth retrying, but they leave the handle as it was, rather than spending it.
+    /// If the [`Requester`] stops waiting while this is sending, the handle is spent anyway
+    pub fn try_respond(&mut self, response: Resp) -> Result<(), Resp> {
+        if self.notification {
+            return Ok(());
+        }
+        if (self.expires && self.is_expired()) || self.is_canceled() {
+            self.settle(&response);
+            return Err(response);
+        }
+        let response_sender = match self.response_sender.take() {
+            Some(response_sender) => response_sender,
+            None => return Err(response),
+        };
+        let reply = self.reply(response, None);
+        response_sender.send(reply).map_err(Reply::into_unsent)?;
+        self.record_responded();
+        // Nothing else will happen to the request, so stop counting it
+        self.trackers.clear();
+        self.metrics = None;
+        Ok(())
+    }
+
+    /// Respond to the [`Requester`]'s request, and wait until it has taken the response, e.g. to only mark work done once it was delivered.
+    /// Fails like [`UnRespondedRequest::respond`], or if the [`Requester`] stopped waiting before taking the response,
+    /// and returns your response back.
+    /// See [`ReceivedRequest::respond_confirmed`]
+    pub async fn respond_confirmed(mut self, response: Resp) -> Result<(), Resp> {
+        if self.notification {
+            return Ok(());
+        }
+        if self.expires && self.is_expired() {
+            self.settle(&response);
+            return Err(response);
+        }
+        let response_sender = match self.response_sender.take() {
+            Some(response_sender) => response_sender,
+            None => return Err(response),
+        };
+        let (ack_sender, ack_receiver) = oneshot::channel();
+        let reply = self.reply(response, Some(ack_sender));
+        response_sender.send(reply).map_err(Reply::into_unsent)?;
+        self.record_responded();
+        // A Reply always acknowledges when it is dropped, so the ack can only be cancelled if the Reply was leaked
+        match ack_receiver.await {
+            Ok(Some(response)) => Err(response),
+            Ok(None) | Err(oneshot::Canceled) => Ok(()),
+        }
+    }
+
+    /// The token the [`Requester`] attached with [`Requester::send_idempotent`], if any
+    pub fn idempotency_token(&self) -> Option<u128> {
+        self.idempotency_token
+    }
+
+    /// The correlation id assigned by an [`IdRequester`], if any
+    pub fn id(&self) -> Option<RequestId> {
+        self.id
+    }
+
+    /// The [`Requester`]'s span when the request was made.
+    /// For [`Requester::send`], this is its `send` span.
+    /// Enter it (or make it the parent of your own) so that handling the request shows up under the [`Requester`]'s work
+    #[cfg(feature = "tracing")]
+    pub fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
+    /// The metadata the [`Requester`] attached with [`Requester::send_with`].
+    /// For other ways of sending, this is `M::default()`
+    pub fn meta(&self) -> &M {
+        &self.meta
+    }
+
+    fn reply(&mut self, response: Resp, ack: Option<oneshot::Sender<Option<Resp>>>) -> Reply<Resp> {
+        self.settle(&response);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(parent: &self.span, id = ?self.id, "responded");
+        Reply::new(response, ack, self.stamps())
+    }
+
+    /// Run the `on_respond` hook once a response is decided, whether or not it can be delivered.
+    /// Others may be waiting on it, e.g. retries of an [idempotent](ResponderExt::idempotent) request whose first requester gave up
+    fn settle(&mut self, response: &Resp) {
+        if let Some(on_respond) = self.on_respond.take() {
+            on_respond(response)
+        }
+    }
+
+    fn record_responded(&mut self) {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.responded()
+        }
+    }
+
+    /// Tell the [`Requester`] that the request was evicted from the channel
+    fn shed(mut self) {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.shed()
+        }
+        if let Some(response_sender) = self.response_sender.take() {
+            let _ = response_sender.send(Reply::shed());
+        }
+    }
+
+    /// Record whether the last attempt to put the request on the channel found it full
+    fn found_full(&mut self, full: bool) {
+        if let Some(metrics) = &mut self.metrics {
+            metrics.found_full(full)
+        }
+    }
+
+    /// Let a [`rendezvous`] requester know that the request was received
+    fn pick_up(&mut self, queue_depth: usize) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(parent: &self.span, id = ?self.id, "received");
+        self.slot = None;
+        self.queue_depth = queue_depth;
+        if let Some(metrics) = &mut self.metrics {
+            metrics.received()
+        }
+        if let Some(picked_up) = self.picked_up.take() {
+            let _ = picked_up.send(());
+        }
+    }
+
+    /// Whether the [`Requester`] has stopped waiting for the response (e.g. with [`CancelHandle::cancel`]),
+    /// so responding would fail and any remaining work can be skipped
+    pub fn is_canceled(&self) -> bool {
+        self.response_sender
+            .as_ref()
+            .is_none_or(oneshot::Sender::is_canceled)
+    }
+
+    /// Whether the [`Requester`] is still waiting for the response.
+    /// The opposite of [`UnRespondedRequest::is_canceled`]
+    pub fn is_requester_waiting(&self) -> bool {
+        !self.is_canceled()
+    }
+
+    /// Wait until the [`Requester`] stops waiting for the response, e.g. because it dropped its future.
+    /// `select` this against expensive work, to abandon it early
+    ///
+    /// ```
+    /// # use futures::{future::{select, Either}, join};
+    /// # async_std::task::block_on(async {
+    /// # let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(1);
+    /// # let (response, received) = join!(requester.request("hello"), responder.recv());
+    /// # drop(response);
+    /// let (request, mut unresponded) = received.unwrap().split();
+    /// let work = Box::pin(async { std::future::pending::<usize>().await });
+    /// let cancelled = Box::pin(unresponded.wait_for_cancellation());
+    /// match select(work, cancelled).await {
+    ///     Either::Left((len, _)) => unreachable!(),
+    ///     Either::Right(_) => (), // Nobody's listening, so give up
+    /// }
+    /// # drop(request);
+    /// # })
+    /// ```
+    pub async fn wait_for_cancellation(&mut self) {
+        if let Some(response_sender) = &mut self.response_sender {
+            response_sender.cancellation().await
+        }
+    }
+
+    /// Forget a request which never reached the channel.
+    /// Nobody had the chance to respond, so dropping it isn't warned about
+    pub(crate) fn refuse(&mut self) {
+        self.response_sender = None;
+    }
+
+    fn stamps(&self) -> Option<Stamps> {
+        self.received_at.map(|received| Stamps {
+            received,
+            responded: Instant::now(),
+        })
+    }
+}
+
+impl<Resp, M> Drop for UnRespondedRequest<Resp, M> {
+    /// The [`Requester`] sees [`SendRequestError::Ignored`] as soon as this is dropped.
+    /// `#[must_use]` can't catch every forgotten response, so warn about it here
+    fn drop(&mut self) {
+        if let Some(response_sender) = self.response_sender.take() {
+            if !response_sender.is_canceled() {
+                log::warn!(
+                    "An UnRespondedRequest was dropped without responding, while its Requester was waiting"
+                );
+            }
+        }
+    }
+}
+
+/// When the [`Responder`] received and responded to a request
+#[derive(Clone, Copy)]
+struct Stamps {
+    received: Instant,
+    responded: Instant,
+}
+
+/// What actually travels back to the [`Requester`].
+/// If an acknowledgement was asked for, it is sent when the [`Reply`] is dropped:
+/// with nothing if the response was taken, or with the response if it was never taken.
+/// A [shed](Reply::shed) request is answered with no response at all.
+struct Reply<Resp> {
+    response: Option<Resp>,
+    ack: Option<oneshot::Sender<Option<Resp>>>,
+    stamps: Option<Stamps>,
+}
+
+impl<Resp> Reply<Resp> {
+    fn new(
+        response: Resp,
+        ack: Option<oneshot::Sender<Option<Resp>>>,
+        stamps: Option<Stamps>,
+    ) -> Self {
+        Self {
+            response: Some(response),
+            ack,
+            stamps,
+        }
+    }
+
+    fn shed() -> Self {
+        Self {
+            response: None,
+            ack: None,
+            stamps: None,
+        }
+    }
+
+    fn into_response(mut self) -> Result<Resp, ResponseError> {
+        self.response.take().ok_or(ResponseError::Shed)
+    }
+
+    /// Take back a response which never reached the [`Requester`]
+    fn into_unsent(mut self) -> Resp {
+        self.response.take().expect("Only a sent Reply can be shed")
+    }
+}
+
+impl<Resp> Drop for Reply<Resp> {
+    fn drop(&mut self) {
+        if let Some(ack) = self.ack.take() {
+            let _ = ack.send(self.response.take());
+        }
     }
 }
 
 /// Represents the request.
 /// This implements [`AsRef`] and [`AsMut`] for the request itself for explicit use.
 /// Alternatively, you may use [`Deref`] and [`DerefMut`] either explicitly, or coerced.
-/// Must be used by calling [`ReceivedRequest::respond`], or destructured.
+/// Must be used by calling [`ReceivedRequest::respond`], or [split](ReceivedRequest::split) to respond separately.
 #[must_use = "You must respond to the request"]
 #[derive(AsRef, AsMut, Deref, DerefMut)]
-pub struct ReceivedRequest<Req, Resp> {
+pub struct ReceivedRequest<Req, Resp, M = ()> {
     /// The request itself
     #[as_ref]
     #[as_mut]
@@ -77,66 +705,1191 @@ pub struct ReceivedRequest<Req, Resp> {
     #[deref_mut]
     pub request: Req,
     /// Handle to respond to the [`Requester`]
-    pub unresponded: UnRespondedRequest<Resp>,
+    pub unresponded: UnRespondedRequest<Resp, M>,
 }
 
-impl<Req, Resp> ReceivedRequest<Req, Resp> {
-    /// Respond to the [`Requester`]'s request, and take ownership of it
-    /// Fails if the associated [`Requester`] was dropped, and returns your response back
+impl<Req, Resp, M> ReceivedRequest<Req, Resp, M> {
+    /// Respond to the [`Requester`]'s request, and take ownership of it.
+    /// Fails, and returns the request and your response back, if:
+    /// - the [`Requester`] stopped waiting for the response, because it was dropped, timed out,
+    ///   or the request was cancelled with a [`CancelHandle`] (see [`UnRespondedRequest::is_canceled`])
+    /// - the request was sent with [`Requester::send_deadline`], and the deadline has passed
     pub fn respond(self, response: Resp) -> Result<Req, (Req, Resp)> {
         match self.unresponded.respond(response) {
             Ok(_) => Ok(self.request),
             Err(response) => Err((self.request, response)),
         }
     }
+
+    /// Split off the handle to respond with, keeping ownership of the request.
+    /// This lets you unblock the [`Requester`] as soon as the response is ready, and carry on working with the request afterwards:
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// # let (requester, responder) = bidirectional_channel::bounded::<String, usize>(1);
+    /// # let (response, ()) = join!(requester.send(String::from("hello")), async {
+    /// let (request, unresponded) = responder.recv().await.unwrap().split();
+    /// unresponded.respond(request.len()).unwrap();
+    /// // The requester already has its response
+    /// drop(request); // Cleanup, and so on
+    /// # });
+    /// # assert!(response.unwrap() == 5);
+    /// # })
+    /// ```
+    pub fn split(self) -> (Req, UnRespondedRequest<Resp, M>) {
+        (self.request, self.unresponded)
+    }
+
+    /// Respond to the [`Requester`]'s request, keeping the request borrowable (e.g. for logging it afterwards).
+    /// Once this succeeds, the handle is spent, so responding again fails, and returns your response back.
+    /// Fails like [`UnRespondedRequest::try_respond`], and returns your response back
+    pub fn respond_ref(&mut self, response: Resp) -> Result<(), Resp> {
+        self.unresponded.try_respond(response)
+    }
+
+    /// Respond to the [`Requester`]'s request, and take ownership of it.
+    /// Fails like [`UnRespondedRequest::try_respond`], and returns the whole [`ReceivedRequest`] back alongside your response,
+    /// so you can decide whether to retry, log, or keep hold of it
+    // The request is returned intact, so it can't be boxed
+    #[allow(clippy::result_large_err)]
+    pub fn try_respond(mut self, response: Resp) -> Result<Req, (Self, Resp)> {
+        match self.unresponded.try_respond(response) {
+            Ok(()) => Ok(self.request),
+            Err(response) => Err((self, response)),
+        }
+    }
+
+    /// Compute the response from the request, respond with it, and take ownership of the request.
+    /// Fails like [`ReceivedRequest::respond`], and returns the computed response back
+    pub fn respond_with<F>(self, f: F) -> Result<Req, (Req, Resp)>
+    where
+        F: FnOnce(&Req) -> Resp,
+    {
+        let response = f(&self.request);
+        self.respond(response)
+    }
+
+    /// Respond to the [`Requester`]'s request, and take ownership of it once the [`Requester`] has taken the response.
+    /// This is stricter than [`ReceivedRequest::respond`], which only checks that the [`Requester`] is still waiting.
+    /// Fails like [`ReceivedRequest::respond`], or if the [`Requester`] stopped waiting before taking the response,
+    /// and returns your response back
+    pub async fn respond_confirmed(self, response: Resp) -> Result<Req, (Req, Resp)> {
+        match self.unresponded.respond_confirmed(response).await {
+            Ok(_) => Ok(self.request),
+            Err(response) => Err((self.request, response)),
+        }
+    }
+
+    /// Respond to the [`Requester`]'s request only if it is still waiting, and take ownership of the request.
+    /// Unlike [`ReceivedRequest::respond`], this doesn't attempt to send if the [`Requester`] has already gone,
+    /// so you can tell that apart from a send that was attempted and failed.
+    pub fn respond_if_waiting(mut self, response: Resp) -> RespondOutcome<Req, Resp> {
+        if self.unresponded.is_canceled() {
+            self.unresponded.settle(&response);
+            return RespondOutcome::SkippedGone(self.request, response);
+        }
+        match self.respond(response) {
+            Ok(request) => RespondOutcome::Delivered(request),
+            Err((request, response)) => RespondOutcome::Failed(request, response),
+        }
+    }
+
+    /// The deadline the [`Requester`] attached with [`Requester::send_before`] or [`Requester::send_deadline`], if any.
+    /// Pass this to the [`Requester::send_before`] of any sub-requests to propagate it
+    pub fn deadline(&self) -> Option<Instant> {
+        self.unresponded.deadline()
+    }
+
+    /// How many requests were still waiting on the channel behind this one when the [`Responder`] received it.
+    /// See [`UnRespondedRequest::queue_depth`]
+    pub fn queue_depth(&self) -> usize {
+        self.unresponded.queue_depth()
+    }
+
+    /// Whether the deadline has passed, so it may not be worth doing the work.
+    /// This is `false` if there is no deadline
+    pub fn is_expired(&self) -> bool {
+        self.unresponded.is_expired()
+    }
+
+    /// How much time is left before the deadline, if there is one.
+    /// This is zero once the deadline has passed
+    pub fn remaining(&self) -> Option<Duration> {
+        self.unresponded.remaining()
+    }
+
+    /// The token the [`Requester`] attached with [`Requester::send_idempotent`], if any
+    pub fn idempotency_token(&self) -> Option<u128> {
+        self.unresponded.idempotency_token()
+    }
+
+    /// The correlation id assigned by an [`IdRequester`], if any
+    pub fn id(&self) -> Option<RequestId> {
+        self.unresponded.id()
+    }
+
+    /// The [`Requester`]'s span when the request was made, see [`UnRespondedRequest::span`]
+    #[cfg(feature = "tracing")]
+    pub fn span(&self) -> &tracing::Span {
+        self.unresponded.span()
+    }
+
+    /// Whether this was sent with [`Requester::notify`], so nobody is waiting for a response
+    pub fn is_notification(&self) -> bool {
+        self.unresponded.notification
+    }
+
+    /// The metadata the [`Requester`] attached with [`Requester::send_with`].
+    /// See [`UnRespondedRequest::meta`]
+    pub fn meta(&self) -> &M {
+        self.unresponded.meta()
+    }
+
+    /// Take back a request which the channel refused, see [`UnRespondedRequest::refuse`]
+    pub(crate) fn into_refused(mut self) -> Req {
+        self.unresponded.refuse();
+        self.request
+    }
 }
 
-impl<Req, Resp> Into<(Req, UnRespondedRequest<Resp>)> for ReceivedRequest<Req, Resp> {
-    fn into(self) -> (Req, UnRespondedRequest<Resp>) {
+/// The result of [`ReceivedRequest::respond_if_waiting`]
+#[derive(Debug)]
+pub enum RespondOutcome<Req, Resp> {
+    /// The response was sent to the [`Requester`]
+    Delivered(Req),
+    /// The [`Requester`] had already gone, so no attempt was made to send the response
+    SkippedGone(Req, Resp),
+    /// The [`Requester`] went away while the response was being sent
+    Failed(Req, Resp),
+}
+
+// Only the request body needs to be `Debug`
+impl<Req: Debug, Resp, M> Debug for ReceivedRequest<Req, Resp, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReceivedRequest")
+            .field("request", &self.request)
+            .field("unresponded", &self.unresponded)
+            .finish()
+    }
+}
+
+/// Equivalent to [`ReceivedRequest::split`]
+impl<Req, Resp, M> From<ReceivedRequest<Req, Resp, M>> for (Req, UnRespondedRequest<Resp, M>) {
+    fn from(received: ReceivedRequest<Req, Resp, M>) -> Self {
         let ReceivedRequest {
             request,
             unresponded,
-        } = self;
+        } = received;
         (request, unresponded)
     }
 }
+/// A request [`Requester::poll_send`] is holding until there's space on the channel
+struct PendingSend<Req, Resp, M> {
+    /// Dropped before the request, so that an abandoned request isn't warned about
+    response_receiver: oneshot::Receiver<Reply<Resp>>,
+    received: ReceivedRequest<Req, Resp, M>,
+    space: Option<EventListener>,
+}
+
 /// Represents the initiator for the request-response exchange
-#[derive(Clone)]
-pub struct Requester<Req, Resp> {
-    outgoing: channel::Sender<ReceivedRequest<Req, Resp>>,
+pub struct Requester<Req, Resp, M = ()> {
+    /// Shared between clones, so that [`WeakRequester`]s can tell when the last one is dropped
+    outgoing: Arc<Outgoing<Req, Resp, M>>,
+    /// Set by [`rendezvous`]
+    rendezvous: bool,
+    /// Shared with the [`Responder`], see [`Requester::in_flight`]
+    in_flight: Arc<InFlightCount>,
+    /// Held by [`Requester::poll_send`] while the channel is full.
+    /// Not shared between clones.
+    /// Only used through `&mut self`, but the [`Mutex`] keeps [`Requester`] `Sync` without `Req: Sync`
+    pending: Mutex<Option<PendingSend<Req, Resp, M>>>,
+    /// Set by [`Requester::with_metrics`]
+    metrics: Option<MetricsSink>,
+}
+
+// Requests and responses needn't be `Clone` for the handle to be
+impl<Req, Resp, M> Clone for Requester<Req, Resp, M> {
+    fn clone(&self) -> Self {
+        Self {
+            outgoing: self.outgoing.clone(),
+            rendezvous: self.rendezvous,
+            in_flight: self.in_flight.clone(),
+            pending: Mutex::new(None),
+            metrics: self.metrics.clone(),
+        }
+    }
 }
 
-impl<Req, Resp> Requester<Req, Resp> {
+// A request held by `poll_send` is moved onto the channel by value, never borrowed in place
+impl<Req, Resp, M> Unpin for Requester<Req, Resp, M> {}
+
+impl<Req, Resp, M> Debug for Requester<Req, Resp, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("Requester");
+        // Only named channels show their name
+        if let Some(name) = self.name() {
+            f.field("name", &name);
+        }
+        f.field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .field("responders_alive", &!self.is_closed())
+            .finish()
+    }
+}
+
+// Every way of making a request, besides `send_with`, attaches `M::default()` as its metadata
+impl<Req, Resp, M: Default> Requester<Req, Resp, M> {
+    /// Put a request on the channel, waiting while it's full.
+    /// Returns a [`Response`] to `await` separately, so responses may be collected in any order.
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub async fn request(&self, request: Req) -> Result<Response<Resp>, EnqueueError<Req>> {
+        self.enqueue(request).await.map(Response::new)
+    }
+
     /// Make a request.
     /// `await` the result to receive the response.
-    pub async fn send(&self, request: Req) -> Result<Resp, SendRequestError<Req>> {
-        // Create the return path
-        let (response_sender, response_receiver) = oneshot::channel();
+    /// This is equivalent to [`Requester::request`] followed by awaiting the [`Response`].
+    pub fn send(&self, request: Req) -> SendFuture<'_, Req, Resp, M> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "send",
+            channel = self.name(),
+            len = self.len(),
+            blocked = tracing::field::Empty
+        );
+        // The request captures the current span, for the responder to link back to
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        let send = SendFuture::new(self.outgoing.send(received), response_receiver);
+        #[cfg(feature = "tracing")]
+        let send = send.instrument(span.clone());
+        send
+    }
+
+    /// Make a request, finding out when it is accepted onto the channel separately from when it is responded to.
+    /// The [`EnqueuedConfirmation`] puts the request on the channel, so must be driven first.
+    /// It fails with [`EnqueueError::Closed`] if the [`Responder`] was dropped,
+    /// and the [`Response`] fails with [`ResponseError::Ignored`] if the request wasn't responded to (including if it was never enqueued).
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// # let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(1);
+    /// let (enqueued, response) = requester.send_and_confirm("hello");
+    /// enqueued.await.unwrap();
+    /// // Update the UI, and so on
+    /// # let (response, _) = join!(response, async { responder.recv().await.unwrap().respond(5).unwrap() });
+    /// # assert!(response == Ok(5));
+    /// # })
+    /// ```
+    pub fn send_and_confirm(
+        &self,
+        request: Req,
+    ) -> (EnqueuedConfirmation<'_, Req, Resp, M>, Response<Resp>) {
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        (
+            EnqueuedConfirmation::new(self.outgoing.send(received)),
+            Response::new(response_receiver),
+        )
+    }
+
+    /// Put a request on the channel if there's room, without waiting.
+    /// Returns a [`Response`] to `await` later.
+    /// Fails if the channel is full, or the [`Responder`] was dropped, and returns your request back
+    pub fn try_send(&self, request: Req) -> Result<Response<Resp>, TrySendError<Req>> {
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        match self.outgoing.try_send(received) {
+            Ok(()) => Ok(Response::new(response_receiver)),
+            Err(channel::TrySendError::Full(returned)) => {
+                Err(TrySendError::Full(returned.into_refused()))
+            }
+            Err(channel::TrySendError::Closed(returned)) => {
+                Err(TrySendError::Closed(returned.into_refused()))
+            }
+        }
+    }
+
+    /// Put a request on the channel without waiting, evicting the oldest request if it's full.
+    /// This caps the channel under bursts where fresh requests matter more than stale ones, like telemetry.
+    /// The new request joins the back of the channel as usual.
+    /// The evicted request's [`Requester`] fails with [`SendRequestError::Shed`] (or [`ResponseError::Shed`]),
+    /// and the [`Responder`] never sees it.
+    /// Requests which were already received, including [peeked](Responder::peek) ones, are never evicted.
+    /// On a [`rendezvous`] channel, this doesn't wait for the [`Responder`] to receive the request.
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub fn send_overwrite(&self, request: Req) -> Result<Response<Resp>, EnqueueError<Req>> {
+        let (mut received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        loop {
+            match self.outgoing.try_send(received) {
+                Ok(()) => return Ok(Response::new(response_receiver)),
+                Err(channel::TrySendError::Closed(returned)) => {
+                    return Err(EnqueueError::Closed(returned.into_refused()))
+                }
+                Err(channel::TrySendError::Full(returned)) => {
+                    received = returned;
+                    // If a Responder took it first, there's space anyway
+                    self.outgoing.shed_oldest();
+                }
+            }
+        }
+    }
+
+    /// Put a request on the channel from a hand-written [`Future::poll`], registering `cx` to be woken while it's full.
+    /// `request` is taken by the first call.
+    /// While this returns [`Poll::Pending`], this [`Requester`] holds on to it, so call again with `None`,
+    /// or take it back with [`Requester::abandon_send`] to give up.
+    /// The returned [`Response`] can be polled in the same way.
+    /// On a [`rendezvous`] channel, this doesn't wait for the [`Responder`] to receive the request.
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    ///
+    /// # Panics
+    /// If `request` is `None` and no request is held, or `Some` while one is
+    pub fn poll_send(
+        &mut self,
+        cx: &mut Context<'_>,
+        request: &mut Option<Req>,
+    ) -> Poll<Result<Response<Resp>, EnqueueError<Req>>> {
+        let mut pending = match (lock(&self.pending).take(), request.take()) {
+            (Some(pending), None) => pending,
+            (None, Some(request)) => {
+                let (received, response_receiver) =
+                    self.prepare_counted(request, M::default(), |_| ());
+                PendingSend {
+                    response_receiver,
+                    received,
+                    space: None,
+                }
+            }
+            (None, None) => panic!("poll_send called without a request"),
+            (Some(_), Some(_)) => panic!("poll_send called with a request while holding another"),
+        };
+        loop {
+            match self.outgoing.try_send(pending.received) {
+                Ok(()) => return Poll::Ready(Ok(Response::new(pending.response_receiver))),
+                Err(channel::TrySendError::Closed(returned)) => {
+                    return Poll::Ready(Err(EnqueueError::Closed(returned.into_refused())))
+                }
+                Err(channel::TrySendError::Full(returned)) if self.outgoing.rejects() => {
+                    return Poll::Ready(Err(EnqueueError::Full(returned.into_refused())))
+                }
+                Err(channel::TrySendError::Full(returned)) => pending.received = returned,
+            }
+            match &mut pending.space {
+                // Space may have been made before we started listening, so check again
+                None => pending.space = Some(self.outgoing.listen()),
+                Some(space) => match Pin::new(space).poll(cx) {
+                    Poll::Ready(()) => pending.space = None,
+                    Poll::Pending => {
+                        *lock(&self.pending) = Some(pending);
+                        return Poll::Pending;
+                    }
+                },
+            }
+        }
+    }
+
+    /// Make every request in `requests`, and collect their responses in the same order.
+    /// Requests are put on the channel one after another, waiting while it's full, and then their responses are awaited together.
+    /// Each request fails separately, like [`Requester::send`]:
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(2);
+    /// let handler = responder.serve_fn(|request| async move { request.len() });
+    /// let responses = async move { requester.send_all(vec!["hello", "hi"]).await };
+    /// let (responses, ()) = join!(responses, handler);
+    /// assert!(responses == [Ok(5), Ok(2)]);
+    /// # })
+    /// ```
+    pub async fn send_all(
+        &self,
+        requests: impl IntoIterator<Item = Req>,
+    ) -> Vec<Result<Resp, SendRequestError<Req>>> {
+        let mut pending = Vec::new();
+        for request in requests {
+            pending.push(self.request(request).await);
+        }
+        let mut responses = Vec::with_capacity(pending.len());
+        for response in pending {
+            responses.push(match response {
+                Ok(response) => response.await.map_err(SendRequestError::from),
+                Err(e) => Err(e.into()),
+            });
+        }
+        responses
+    }
+
+    /// Like [`Requester::send_all`], but stops at the first failure, and returns it.
+    /// Requests after a failed one aren't sent, and the responses to requests already sent are discarded
+    pub async fn send_all_ok(
+        &self,
+        requests: impl IntoIterator<Item = Req>,
+    ) -> Result<Vec<Resp>, SendRequestError<Req>> {
+        let mut pending = Vec::new();
+        for request in requests {
+            match self.request(request).await {
+                Ok(response) => pending.push(response),
+                Err(e) => return Err(e.into()),
+            }
+        }
+        let mut responses = Vec::with_capacity(pending.len());
+        for response in pending {
+            responses.push(response.await?);
+        }
+        Ok(responses)
+    }
+
+    /// Put a request on the channel, returning a response future which may be cloned and awaited by several consumers.
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub async fn send_shared(
+        &self,
+        request: Req,
+    ) -> Result<Shared<Response<Resp>>, EnqueueError<Req>>
+    where
+        Resp: Clone,
+    {
+        let response_receiver = self.enqueue(request).await?;
+        Ok(Response::new(response_receiver).shared())
+    }
+
+    /// Put a request on the channel without waiting for a response.
+    /// The [`Responder`] still receives a [`ReceivedRequest`], but responding to it will fail, returning the response back,
+    /// unless the responses are being collected by [`Requester::responses`].
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub async fn send_and_forget(&self, request: Req) -> Result<(), EnqueueError<Req>> {
+        let response_receiver = self.enqueue(request).await?;
         self.outgoing
-            .send(ReceivedRequest {
-                request,
-                unresponded: UnRespondedRequest { response_sender },
-            })
-            .await
-            .map_err(|e| SendRequestError::Closed(e.into_inner().request))?;
-        let response = response_receiver
+            .collector
+            .collect(Response::new(response_receiver));
+        Ok(())
+    }
+
+    /// Make a request which should be responded to before `deadline`.
+    /// The [`Responder`] can read the deadline with [`ReceivedRequest::deadline`], and pass it on to its own sub-requests,
+    /// so that the whole call chain shares one time budget.
+    /// Passing `None` sends without a deadline, which is convenient when propagating.
+    ///
+    /// The deadline is advisory - this still waits for the response, however late.
+    /// Deadlines are [`Instant`]s, so they are only meaningful within this process.
+    pub fn send_before(
+        &self,
+        request: Req,
+        deadline: impl Into<Option<Instant>>,
+    ) -> SendFuture<'_, Req, Resp, M> {
+        let deadline = deadline.into();
+        let (received, response_receiver) =
+            self.prepare_counted(request, M::default(), |unresponded| {
+                unresponded.deadline = deadline
+            });
+        SendFuture::new(self.outgoing.send(received), response_receiver)
+    }
+
+    /// Make a request tagged with `token` (e.g. a UUID), which should be the same every time this request is retried.
+    /// A [`Responder`] using [`ResponderExt::idempotent`] answers duplicate tokens from its cache, rather than handling them again.
+    /// Other [`Responder`]s ignore the token.
+    pub fn send_idempotent(&self, token: u128, request: Req) -> SendFuture<'_, Req, Resp, M> {
+        let (received, response_receiver) =
+            self.prepare_counted(request, M::default(), |unresponded| {
+                unresponded.idempotency_token = Some(token)
+            });
+        SendFuture::new(self.outgoing.send(received), response_receiver)
+    }
+
+    /// Make a request, giving up if the round trip takes longer than `timeout`.
+    /// Fails with [`SendRequestError::TimedOut`] if the channel stayed full, and returns your request back,
+    /// or with [`SendRequestError::ResponseTimedOut`] if the request was on the channel, but not responded to in time.
+    /// A late response is returned back to the [`Responder`].
+    pub async fn send_timeout(
+        &self,
+        request: Req,
+        timeout: Duration,
+    ) -> Result<Resp, SendRequestError<Req>> {
+        let deadline = Instant::now() + timeout;
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), |_| ());
+        self.send_until(received, response_receiver, deadline).await
+    }
+
+    /// Make a request which expires at `deadline`.
+    /// The [`Responder`] can read the deadline with [`ReceivedRequest::deadline`], and check [`ReceivedRequest::is_expired`] to skip late work.
+    /// Responding after the deadline fails.
+    ///
+    /// Fails like [`Requester::send_timeout`] once the deadline passes, whatever the [`Responder`] is doing.
+    pub async fn send_deadline(
+        &self,
+        request: Req,
+        deadline: Instant,
+    ) -> Result<Resp, SendRequestError<Req>> {
+        let (received, response_receiver) =
+            self.prepare_counted(request, M::default(), |unresponded| {
+                unresponded.deadline = Some(deadline);
+                unresponded.expires = true;
+            });
+        self.send_until(received, response_receiver, deadline).await
+    }
+
+    /// Make a request, and report where the time went.
+    /// `await` the result to receive the response, alongside a [`ResponseTimings`] breakdown.
+    /// The [`Responder`] must receive with [`ResponderExt::recv_stamped`] for the queue and processing times to be recorded.
+    pub async fn send_detailed(
+        &self,
+        request: Req,
+    ) -> Result<ResponseTimings<Resp>, SendRequestError<Req>> {
+        let started = Instant::now();
+        let response_receiver = self.enqueue(request).await?;
+        let enqueued = Instant::now();
+        let mut response = response_receiver
             .await
             .map_err(|_| SendRequestError::Ignored)?;
-        Ok(response)
+        let stamps = response.stamps.take();
+        Ok(ResponseTimings {
+            response: response.into_response()?,
+            enqueue_wait: enqueued - started,
+            queue_time: stamps.map(|stamps| stamps.received.saturating_duration_since(enqueued)),
+            processing_time: stamps.map(|stamps| stamps.responded - stamps.received),
+        })
+    }
+
+    /// Make a request, blocking the current thread until the response arrives.
+    /// See [`Requester::send`].
+    /// **Do not call this from within an async context**: it will block the executor thread until it completes.
+    ///
+    /// ```
+    /// # let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(1);
+    /// let responder = std::thread::spawn(move || {
+    ///     let request = responder.recv_blocking().unwrap();
+    ///     let len = request.len();
+    ///     request.respond(len).unwrap();
+    /// });
+    /// assert!(requester.send_blocking("hello").unwrap() == 5);
+    /// # responder.join().unwrap();
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(&self, request: Req) -> Result<Resp, SendRequestError<Req>> {
+        futures::executor::block_on(self.send(request))
+    }
+
+    /// Put the request on the channel, returning the receiving half of the return path.
+    /// Fails if the [`Responder`] was dropped, and returns the request back
+    async fn enqueue(
+        &self,
+        request: Req,
+    ) -> Result<oneshot::Receiver<Reply<Resp>>, EnqueueError<Req>> {
+        self.enqueue_with(request, |_| ()).await
+    }
+
+    /// [`Requester::enqueue`], with a chance to attach metadata to the [`UnRespondedRequest`]
+    async fn enqueue_with(
+        &self,
+        request: Req,
+        configure: impl FnOnce(&mut UnRespondedRequest<Resp, M>),
+    ) -> Result<oneshot::Receiver<Reply<Resp>>, EnqueueError<Req>> {
+        let (received, response_receiver) = self.prepare_counted(request, M::default(), configure);
+        self.put(received).await?;
+        Ok(response_receiver)
+    }
+}
+
+impl<Req, Resp, M> Requester<Req, Resp, M> {
+    /// Collect the responses to requests sent with [`Requester::send_and_forget`] from now on, by any [`Requester`] on this channel,
+    /// including through a [`RequestSink`].
+    /// Only one [`Responses`] collects at a time: calling this again ends the previous one, once its responses have arrived.
+    /// Stops collecting once the [`Responses`] is dropped
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// # async_std::task::block_on(async {
+    /// let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(2);
+    /// let responses = requester.responses();
+    /// requester.send_and_forget("hello").await.unwrap();
+    /// requester.send_and_forget("world!").await.unwrap();
+    /// drop(requester);
+    /// while let Ok(request) = responder.recv().await {
+    ///     let len = request.len();
+    ///     request.respond(len).unwrap();
+    /// }
+    /// assert!(responses.collect::<Vec<_>>().await == [5, 6]);
+    /// # })
+    /// ```
+    pub fn responses(&self) -> Responses<Resp> {
+        self.outgoing.collector.responses()
+    }
+
+    /// Make a request carrying `meta` alongside it (e.g. tracing context, or an auth token), without changing `Req`.
+    /// The type of the metadata is chosen when the channel is created, with [`bounded_with_meta`] or [`unbounded_with_meta`].
+    /// The [`Responder`] reads it with [`ReceivedRequest::meta`].
+    /// The metadata isn't sent back with the response.
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// #[derive(Default)]
+    /// struct TraceId(u64);
+    /// let (requester, responder) = bidirectional_channel::bounded_with_meta::<&str, usize, TraceId>(1);
+    /// let (response, ()) = join!(requester.send_with("hello", TraceId(7)), async {
+    ///     let request = responder.recv().await.unwrap();
+    ///     assert!(request.meta().0 == 7);
+    ///     request.respond(5).unwrap();
+    /// });
+    /// assert!(response.unwrap() == 5);
+    /// # })
+    /// ```
+    pub fn send_with(&self, request: Req, meta: M) -> SendFuture<'_, Req, Resp, M> {
+        let (received, response_receiver) = self.prepare_counted(request, meta, |_| ());
+        SendFuture::new(self.outgoing.send(received), response_receiver)
+    }
+
+    /// Take back the request [`Requester::poll_send`] is holding while the channel is full, if any
+    pub fn abandon_send(&mut self) -> Option<Req> {
+        lock(&self.pending)
+            .take()
+            .map(|pending| pending.received.into_refused())
+    }
+
+    async fn send_until(
+        &self,
+        mut received: ReceivedRequest<Req, Resp, M>,
+        response_receiver: oneshot::Receiver<Reply<Resp>>,
+        deadline: Instant,
+    ) -> Result<Resp, SendRequestError<Req>> {
+        let mut timeout = Delay::new(deadline.saturating_duration_since(Instant::now()));
+        let mut space = None;
+        loop {
+            match self.outgoing.try_send(received) {
+                Ok(()) => break,
+                Err(channel::TrySendError::Full(returned)) if self.outgoing.rejects() => {
+                    return Err(SendRequestError::Full(returned.into_refused()))
+                }
+                Err(channel::TrySendError::Closed(returned)) => {
+                    return Err(SendRequestError::Closed(returned.into_refused()))
+                }
+                Err(channel::TrySendError::Full(returned)) => received = returned,
+            }
+            match space.take() {
+                // Space may have been made before we started listening, so check again
+                None => space = Some(self.outgoing.listen()),
+                Some(listener) => {
+                    if let Either::Right(_) = select(listener, &mut timeout).await {
+                        return Err(SendRequestError::TimedOut(received.into_refused()));
+                    }
+                }
+            }
+        }
+        match select(Response::new(response_receiver), timeout).await {
+            Either::Left((response, _)) => Ok(response?),
+            Either::Right(_) => Err(SendRequestError::ResponseTimedOut),
+        }
+    }
+
+    /// Re-enqueue requests taken from another channel with [`ResponderExt::take_pending`], for a replacement [`Responder`] to handle.
+    /// Each request keeps its own handle, so the original [`Requester`]s are still answered.
+    /// Fails if the [`Responder`] was dropped, and returns the requests which weren't enqueued
+    pub async fn inject(
+        &self,
+        requests: Vec<ReceivedRequest<Req, Resp, M>>,
+    ) -> Result<(), Vec<ReceivedRequest<Req, Resp, M>>> {
+        let mut requests = requests.into_iter();
+        while let Some(request) = requests.next() {
+            if let Err(e) = self.outgoing.send(request).await {
+                return Err(std::iter::once(e.into_inner()).chain(requests).collect());
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that a [`Responder`] is receiving from the channel, without sending it a request, e.g. for a readiness probe.
+    /// The ping waits behind the requests already on the channel, but doesn't take up capacity, and isn't counted by [`Requester::in_flight`] or metrics.
+    /// The [`Responder`] answers it as it takes it off the channel, so it's never handed to your code.
+    /// Returns the round-trip time.
+    /// Fails with [`SendRequestError::Closed`] if the channel is closed, or [`SendRequestError::Ignored`] if the ping was [shed](Requester::send_overwrite).
+    /// Waits for as long as no [`Responder`] is receiving, so use a timeout
+    pub async fn ping(&self) -> Result<Duration, SendRequestError<()>> {
+        let (pong, answered) = oneshot::channel();
+        let sent = Instant::now();
+        if !self.outgoing.ping(pong) {
+            return Err(SendRequestError::Closed(()));
+        }
+        answered.await.map_err(|_| SendRequestError::Ignored)?;
+        Ok(sent.elapsed())
+    }
+
+    /// The name the channel was created with by [`bounded_named`] or [`unbounded_named`], if any
+    pub fn name(&self) -> Option<&str> {
+        self.outgoing.name().map(|name| &**name)
+    }
+
+    /// How many requests are waiting on the channel
+    pub fn len(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    /// Whether there are no requests waiting on the channel
+    pub fn is_empty(&self) -> bool {
+        self.outgoing.is_empty()
+    }
+
+    /// Whether the channel is full, so [`Requester::send`] would wait
+    pub fn is_full(&self) -> bool {
+        self.outgoing.is_full()
+    }
+
+    /// How many requests the channel holds, or `None` if it's unbounded
+    pub fn capacity(&self) -> Option<usize> {
+        self.outgoing.capacity()
+    }
+
+    /// Change how many requests the channel holds, e.g. from an autoscaler.
+    /// This makes an unbounded channel bounded.
+    ///
+    /// Growing takes effect immediately, letting requesters waiting for space onto the channel.
+    /// Shrinking is lazy: no request already on the channel is dropped,
+    /// but new requests wait until the [`Responder`] has received enough that there are fewer than `capacity` left.
+    ///
+    /// # Panics
+    /// If `capacity` is zero
+    pub fn set_capacity(&self, capacity: usize) {
+        self.outgoing.set_capacity(capacity)
+    }
+
+    /// Close the channel for every [`Requester`].
+    /// The [`Responder`] may still receive the requests already on the channel, and then fails to receive.
+    /// Returns `true` if this call closed the channel
+    pub fn close(&self) -> bool {
+        self.outgoing.close()
+    }
+
+    /// Whether the channel is closed
+    pub fn is_closed(&self) -> bool {
+        self.outgoing.is_closed()
+    }
+
+    /// How many [`Requester`]s share this channel, including this one.
+    /// [`WeakRequester`]s aren't counted
+    pub fn sender_count(&self) -> usize {
+        Arc::strong_count(&self.outgoing)
+    }
+
+    /// How many [`Responder`]s share this channel
+    pub fn receiver_count(&self) -> usize {
+        self.outgoing.receiver_count()
+    }
+
+    /// Close the channel, and wait until every request already sent has been responded to, or dropped, e.g. for a clean shutdown.
+    /// This waits for the requests of every [`Requester`] on the channel, as counted by [`Requester::in_flight`].
+    /// Requests waiting for space on the channel fail with [`SendRequestError::Closed`] instead.
+    /// Requests left on the channel when the last [`Responder`] is dropped are dropped with it, so this doesn't wait for them forever
+    pub async fn close_and_wait(self) {
+        self.close();
+        let in_flight = Arc::clone(&self.in_flight);
+        drop(self);
+        in_flight.settled().await
+    }
+
+    /// How many requests have been sent, but not yet responded to or dropped.
+    /// Unlike [`Requester::len`], this includes requests the [`Responder`] has received and is still working on,
+    /// as well as requests waiting for space on the channel
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.get()
+    }
+
+    /// Use this as a [`Sink`](futures::Sink) of requests, for fire-and-forget workloads.
+    /// See [`RequestSink`]
+    pub fn into_sink(self) -> RequestSink<Req, Resp, M> {
+        RequestSink::new(self)
+    }
+
+    /// Report [`RequestMetrics`] to `sink` for every request sent from this handle and its clones, e.g. to feed a latency histogram.
+    /// `sink` is called once each request is responded to, or given up on, so it should be quick.
+    /// Requests sent with [`Requester::notify`] aren't measured
+    ///
+    /// ```
+    /// use bidirectional_channel::{bounded, Outcome};
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// let (requester, responder) = bounded::<&str, usize>(1);
+    /// let requester = requester.with_metrics(|metrics| {
+    ///     assert!(metrics.outcome == Outcome::Responded);
+    ///     println!("took {:?}", metrics.round_trip);
+    /// });
+    /// # let (response, _) = join!(requester.send("hello"), async { responder.recv().await.unwrap().respond(5).unwrap() });
+    /// # assert!(response.unwrap() == 5);
+    /// # })
+    /// ```
+    pub fn with_metrics(mut self, sink: impl Fn(RequestMetrics) + Send + Sync + 'static) -> Self {
+        self.metrics = Some(Arc::new(sink));
+        self
+    }
+
+    /// Create a [`WeakRequester`], which doesn't keep the channel open
+    pub fn downgrade(&self) -> WeakRequester<Req, Resp, M> {
+        WeakRequester {
+            outgoing: Arc::downgrade(&self.outgoing),
+            rendezvous: self.rendezvous,
+            in_flight: self.in_flight.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+
+    /// Put the request on the channel, waiting for the handover on a [`rendezvous`] channel.
+    /// Fails if the [`Responder`] was dropped, and returns the request back
+    async fn put(
+        &self,
+        mut received: ReceivedRequest<Req, Resp, M>,
+    ) -> Result<(), EnqueueError<Req>> {
+        let pickup = match self.rendezvous {
+            true => {
+                let (sender, receiver) = oneshot::channel();
+                received.unresponded.picked_up = Some(sender);
+                Some(receiver)
+            }
+            false => None,
+        };
+        self.outgoing
+            .send(received)
+            .await
+            .map_err(EnqueueError::refused)?;
+        if let Some(pickup) = pickup {
+            // Cancelled if the request was dropped on the channel, which the response reports
+            let _ = pickup.await;
+        }
+        Ok(())
+    }
+
+    /// [`Requester::prepare`], counting the request in [`Requester::in_flight`] until it's responded to or dropped
+    pub(crate) fn prepare_counted(
+        &self,
+        request: Req,
+        meta: M,
+        configure: impl FnOnce(&mut UnRespondedRequest<Resp, M>),
+    ) -> (
+        ReceivedRequest<Req, Resp, M>,
+        oneshot::Receiver<Reply<Resp>>,
+    ) {
+        let in_flight = InFlight::new(self.in_flight.clone());
+        let metrics = self
+            .metrics
+            .clone()
+            .map(|sink| Recorder::new(sink, self.outgoing.name().cloned()));
+        Self::prepare(request, meta, |unresponded| {
+            configure(unresponded);
+            unresponded.trackers.push(Box::new(in_flight));
+            unresponded.metrics = metrics;
+        })
+    }
+
+    /// Wrap the request for the channel, and create its return path
+    pub(crate) fn prepare(
+        request: Req,
+        meta: M,
+        configure: impl FnOnce(&mut UnRespondedRequest<Resp, M>),
+    ) -> (
+        ReceivedRequest<Req, Resp, M>,
+        oneshot::Receiver<Reply<Resp>>,
+    ) {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let mut unresponded = UnRespondedRequest::new(Some(response_sender), meta);
+        configure(&mut unresponded);
+        (
+            ReceivedRequest {
+                request,
+                unresponded,
+            },
+            response_receiver,
+        )
     }
 }
 
+impl<Req, Resp, M: Default> Requester<Arc<Req>, Resp, M> {
+    /// Make a request with a shared reference to `request`, rather than moving it through the channel.
+    /// Only the [`Arc`] is cloned, so a large request can be sent to several channels (e.g. with a [`Broadcaster`](broadcast::Broadcaster))
+    /// without copying it.
+    ///
+    /// The [`Responder`] can only borrow the request, and the request lives until every clone of the [`Arc`] is dropped,
+    /// including the one handed back by [`ReceivedRequest::respond`].
+    /// A [`Responder`] which needs to own it can use [`Arc::try_unwrap`] once it holds the last reference.
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # use std::sync::Arc;
+    /// # async_std::task::block_on(async {
+    /// let (requester, responder) = bidirectional_channel::bounded::<Arc<Vec<u8>>, usize>(1);
+    /// let buffer = Arc::new(vec![0; 1024]);
+    /// # let (response, _) = join!(requester.send_ref(&buffer), async {
+    /// #     let request = responder.recv().await.unwrap();
+    /// #     let len = request.len();
+    /// #     request.respond(len).unwrap()
+    /// # });
+    /// // Once responded to, we have the only reference again
+    /// assert!(response.unwrap() == 1024);
+    /// assert!(Arc::strong_count(&buffer) == 1);
+    /// # })
+    /// ```
+    pub fn send_ref(&self, request: &Arc<Req>) -> SendFuture<'_, Arc<Req>, Resp, M> {
+        self.send(Arc::clone(request))
+    }
+}
+
+impl<Req, M: Default> Requester<Req, (), M> {
+    /// Put a message on the channel when no response is needed, e.g. for events.
+    /// Unlike [`Requester::send_and_forget`], no return path is created for the response:
+    /// the [`Responder`] sees [`ReceivedRequest::is_notification`], and responding to it does nothing.
+    /// Notifications aren't counted in [`Requester::in_flight`].
+    /// Fails if the [`Responder`] was dropped, and returns your request back
+    pub async fn notify(&self, request: Req) -> Result<(), EnqueueError<Req>> {
+        let mut unresponded = UnRespondedRequest::new(None, M::default());
+        unresponded.notification = true;
+        self.put(ReceivedRequest {
+            request,
+            unresponded,
+        })
+        .await
+    }
+}
+
+/// A handle to a channel which doesn't keep it open, created by [`Requester::downgrade`].
+/// Once every [`Requester`] is dropped, the [`Responder`] sees the channel close, and [`WeakRequester::upgrade`] fails
+pub struct WeakRequester<Req, Resp, M = ()> {
+    outgoing: Weak<Outgoing<Req, Resp, M>>,
+    rendezvous: bool,
+    in_flight: Arc<InFlightCount>,
+    metrics: Option<MetricsSink>,
+}
+
+impl<Req, Resp, M> Clone for WeakRequester<Req, Resp, M> {
+    fn clone(&self) -> Self {
+        Self {
+            outgoing: self.outgoing.clone(),
+            rendezvous: self.rendezvous,
+            in_flight: self.in_flight.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+impl<Req, Resp, M> WeakRequester<Req, Resp, M> {
+    /// Get a [`Requester`] back, if there are any left
+    pub fn upgrade(&self) -> Option<Requester<Req, Resp, M>> {
+        self.outgoing.upgrade().map(|outgoing| Requester {
+            outgoing,
+            rendezvous: self.rendezvous,
+            in_flight: self.in_flight.clone(),
+            pending: Mutex::new(None),
+            metrics: self.metrics.clone(),
+        })
+    }
+}
+
+/// The response to a [`Requester::send_detailed`], with a breakdown of the latency
+#[derive(Debug, Clone)]
+pub struct ResponseTimings<Resp> {
+    /// The response itself
+    pub response: Resp,
+    /// How long the [`Requester`] waited for space on the channel
+    pub enqueue_wait: Duration,
+    /// How long the request sat on the channel before the [`Responder`] received it.
+    /// `None` if the [`Responder`] didn't use [`ResponderExt::recv_stamped`]
+    pub queue_time: Option<Duration>,
+    /// How long the [`Responder`] took to respond once it received the request.
+    /// `None` if the [`Responder`] didn't use [`ResponderExt::recv_stamped`]
+    pub processing_time: Option<Duration>,
+}
+
+/// Shared between both ends of the channel, see [`Requester::in_flight`]
+#[derive(Default)]
+pub(crate) struct InFlightCount {
+    count: AtomicUsize,
+    /// Notified whenever the count drops to zero
+    settled: Event,
+}
+
+impl InFlightCount {
+    pub(crate) fn get(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Wait until nothing is in flight
+    async fn settled(&self) {
+        loop {
+            if self.get() == 0 {
+                return;
+            }
+            let listener = self.settled.listen();
+            // The last request may have finished before we started listening
+            if self.get() == 0 {
+                return;
+            }
+            listener.await;
+        }
+    }
+}
+
+/// Counts a request in [`Requester::in_flight`] while it's alive
+struct InFlight(Arc<InFlightCount>);
+
+impl InFlight {
+    fn new(count: Arc<InFlightCount>) -> Self {
+        count.count.fetch_add(1, Ordering::AcqRel);
+        Self(count)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.settled.notify(usize::MAX);
+        }
+    }
+}
+
+/// Lock one of the crate's mutexes, ignoring poisoning.
+/// A panic while one is held (e.g. from a user's `Hash` or `Drop`) can leave an update missing, but never leaves the data invalid,
+/// so it isn't worth spreading the panic to every other handle sharing the data
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Create the ends of a channel holding `capacity` requests, or any number if `None`
+fn pair<Req, Resp, M>(
+    capacity: Option<usize>,
+    rendezvous: bool,
+    name: Option<Arc<str>>,
+    overflow: Overflow,
+) -> (Requester<Req, Resp, M>, Responder<Req, Resp, M>) {
+    assert!(capacity != Some(0), "capacity cannot be zero");
+    let queue: Arc<Queue<Req, Resp, M>> = Arc::new(ChannelBackend::unbounded());
+    let limit = Arc::new(Limit::new(capacity, name));
+    let in_flight = Arc::new(InFlightCount::default());
+    let outgoing = Arc::new(Outgoing::new(queue.clone(), limit.clone(), overflow));
+    let requesters = Arc::downgrade(&outgoing);
+    (
+        Requester {
+            outgoing,
+            rendezvous,
+            in_flight: in_flight.clone(),
+            pending: Mutex::new(None),
+            metrics: None,
+        },
+        Responder::new(queue, in_flight, requesters, limit),
+    )
+}
+
 /// Create a bounded [`Requester`]-[`Responder`] pair.  
-/// That is, once the channel is full, future senders will yield when awaiting until there's space again
-pub fn bounded<Req, Resp>(
-    capacity: usize,
-) -> (Requester<Req, Resp>, Responder<ReceivedRequest<Req, Resp>>) {
-    let (sender, receiver) = channel::bounded(capacity);
-    (Requester { outgoing: sender }, receiver)
+/// That is, once the channel is full, future senders will yield when awaiting until there's space again.
+/// The capacity can be changed later, with [`Requester::set_capacity`].
+///
+/// # Panics
+/// If `capacity` is zero. See [`rendezvous`] instead
+pub fn bounded<Req, Resp>(capacity: usize) -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(Some(capacity), false, None, Overflow::Block)
 }
 
 /// Create an ubounded [`Requester`]-[`Responder`] pair.  
-pub fn unbounded<Req, Resp>() -> (Requester<Req, Resp>, Responder<ReceivedRequest<Req, Resp>>) {
-    let (sender, receiver) = channel::unbounded();
-    (Requester { outgoing: sender }, receiver)
+pub fn unbounded<Req, Resp>() -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(None, false, None, Overflow::Block)
+}
+
+/// Create a bounded [`Requester`]-[`Responder`] pair, named for telling it apart from other channels.
+/// The name appears in both ends' [`Debug`] output, the `tracing` span around [`Requester::send`], and [`RequestMetrics`],
+/// and doesn't change how the channel behaves.
+/// See [`bounded`]
+pub fn bounded_named<Req, Resp>(
+    capacity: usize,
+    name: impl Into<String>,
+) -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(
+        Some(capacity),
+        false,
+        Some(Arc::from(name.into())),
+        Overflow::Block,
+    )
+}
+
+/// Create an unbounded [`Requester`]-[`Responder`] pair, named for telling it apart from other channels.
+/// See [`bounded_named`]
+pub fn unbounded_named<Req, Resp>(
+    name: impl Into<String>,
+) -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(None, false, Some(Arc::from(name.into())), Overflow::Block)
+}
+
+/// Create a bounded [`Requester`]-[`Responder`] pair, where every request carries metadata of type `M` alongside it.
+/// Attach it with [`Requester::send_with`], and read it with [`ReceivedRequest::meta`].
+/// Other ways of sending attach `M::default()`.
+/// Every other channel has `M = ()`.
+/// See [`bounded`]
+///
+/// # Panics
+/// If `capacity` is zero
+pub fn bounded_with_meta<Req, Resp, M>(
+    capacity: usize,
+) -> (Requester<Req, Resp, M>, Responder<Req, Resp, M>) {
+    pair(Some(capacity), false, None, Overflow::Block)
+}
+
+/// Create an unbounded [`Requester`]-[`Responder`] pair, where every request carries metadata of type `M` alongside it.
+/// See [`bounded_with_meta`]
+pub fn unbounded_with_meta<Req, Resp, M>() -> (Requester<Req, Resp, M>, Responder<Req, Resp, M>) {
+    pair(None, false, None, Overflow::Block)
+}
+
+/// Create a bounded [`Requester`]-[`Responder`] pair, which handles requests that don't fit according to `overflow`.
+/// Every way of putting a request on the channel follows the policy, except [`Requester::send_overwrite`], which always evicts.
+/// See [`bounded`]
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// use bidirectional_channel::{bounded_with_policy, Overflow, SendRequestError};
+/// let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::Reject);
+/// let waiting = requester.request("first").await.unwrap();
+/// assert!(requester.send("second").await == Err(SendRequestError::Full("second")));
+/// # drop((waiting, responder));
+/// # })
+/// ```
+///
+/// # Panics
+/// If `capacity` is zero
+pub fn bounded_with_policy<Req, Resp>(
+    capacity: usize,
+    overflow: Overflow,
+) -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(Some(capacity), false, None, overflow)
+}
+
+/// Create a rendezvous [`Requester`]-[`Responder`] pair, where requests are handed straight over.
+/// Putting a request on the channel (e.g. with [`Requester::request`] or [`Requester::send_and_forget`])
+/// only completes once the [`Responder`] has received it, and only one request waits for the handover at a time.
+pub fn rendezvous<Req, Resp>() -> (Requester<Req, Resp>, Responder<Req, Resp>) {
+    pair(Some(1), true, None, Overflow::Block)
+}
+
+/// Create a bounded pair of handles for requests which are answered with a stream of responses, e.g. progress updates or a subscription.
+/// Up to `capacity` requests wait on the channel, and each request buffers up to `response_capacity` responses.
+/// See [`streaming::bounded`]
+///
+/// # Panics
+/// If either capacity is zero
+pub fn bounded_streaming<Req, Item>(
+    capacity: usize,
+    response_capacity: usize,
+) -> (
+    streaming::StreamingRequester<Req, Item>,
+    streaming::StreamingResponder<Req, Item>,
+) {
+    streaming::bounded(capacity, response_capacity)
+}
+
+/// Create an unbounded pair of handles for requests which are answered with a stream of responses.
+/// See [`streaming::unbounded`]
+///
+/// # Panics
+/// If `response_capacity` is zero
+pub fn unbounded_streaming<Req, Item>(
+    response_capacity: usize,
+) -> (
+    streaming::StreamingRequester<Req, Item>,
+    streaming::StreamingResponder<Req, Item>,
+) {
+    streaming::unbounded(response_capacity)
 }
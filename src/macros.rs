@@ -0,0 +1,34 @@
+/// Send a request and await its response, returning early from the surrounding function if it fails.
+///
+/// `request!(requester, request)` evaluates to the response.
+/// On a [`SendRequestError`](crate::SendRequestError), it returns `Err(From::from(error))`,
+/// so the surrounding function can use any error type that converts from it.
+/// ```
+/// use bidirectional_channel::{bounded, request, Requester, SendRequestError};
+/// # use futures::join;
+/// async fn len(requester: &Requester<&'static str, usize>) -> Result<usize, SendRequestError<&'static str>> {
+///     let len = request!(requester, "hello");
+///     Ok(len)
+/// }
+/// # async_std::task::block_on(async {
+/// let (requester, responder) = bounded::<&str, usize>(1);
+/// let responder = async {
+///     let request = responder.recv().await.unwrap();
+///     let len = request.len();
+///     request.respond(len).unwrap()
+/// };
+/// let (len, _) = join!(len(&requester), responder);
+/// assert!(len.unwrap() == 5);
+/// # })
+/// ```
+#[macro_export]
+macro_rules! request {
+    ($requester:expr, $request:expr $(,)?) => {
+        match $requester.send($request).await {
+            ::core::result::Result::Ok(response) => response,
+            ::core::result::Result::Err(error) => {
+                return ::core::result::Result::Err(::core::convert::From::from(error))
+            }
+        }
+    };
+}
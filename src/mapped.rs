@@ -0,0 +1,115 @@
+use crate::{Requester, SendRequestError};
+use std::{sync::Arc, time::Duration};
+
+type MapFn<From, To> = Arc<dyn Fn(From) -> To + Send + Sync>;
+
+/// A [`Requester`] which converts requests and responses at the boundary.
+/// Created by [`Requester::map_request`] and [`Requester::map_response`], which may be chained.
+pub struct MappedRequester<OuterReq, OuterResp, Req, Resp> {
+    inner: Requester<Req, Resp>,
+    map_request: MapFn<OuterReq, Req>,
+    map_response: MapFn<Resp, OuterResp>,
+    /// Set by [`MappedRequester::timeout`]
+    timeout: Option<Duration>,
+}
+
+impl<OuterReq, OuterResp, Req, Resp> Clone for MappedRequester<OuterReq, OuterResp, Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            map_request: self.map_request.clone(),
+            map_response: self.map_response.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<Req, Resp> Requester<Req, Resp> {
+    /// Convert each request with `f` before it's sent
+    pub fn map_request<OuterReq, F>(self, f: F) -> MappedRequester<OuterReq, Resp, Req, Resp>
+    where
+        F: Fn(OuterReq) -> Req + Send + Sync + 'static,
+    {
+        MappedRequester {
+            inner: self,
+            map_request: Arc::new(f),
+            map_response: Arc::new(|response| response),
+            timeout: None,
+        }
+    }
+
+    /// Convert each response with `f` once it's received
+    pub fn map_response<OuterResp, F>(self, f: F) -> MappedRequester<Req, OuterResp, Req, Resp>
+    where
+        F: Fn(Resp) -> OuterResp + Send + Sync + 'static,
+    {
+        MappedRequester {
+            inner: self,
+            map_request: Arc::new(|request| request),
+            map_response: Arc::new(f),
+            timeout: None,
+        }
+    }
+}
+
+impl<OuterReq, OuterResp, Req, Resp> MappedRequester<OuterReq, OuterResp, Req, Resp> {
+    /// Give up on each request if the round trip takes longer than `timeout`, replacing any earlier timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl<OuterReq, OuterResp, Req, Resp> MappedRequester<OuterReq, OuterResp, Req, Resp>
+where
+    OuterReq: 'static,
+    OuterResp: 'static,
+    Req: 'static,
+    Resp: 'static,
+{
+    /// Make a request, converting it and its response.
+    /// `await` the result to receive the response.
+    /// Fails if the [`Responder`](crate::Responder) was dropped, and returns the converted request back,
+    /// or like [`Requester::send_timeout`] if a [timeout](MappedRequester::timeout) is set
+    pub async fn send(&self, request: OuterReq) -> Result<OuterResp, SendRequestError<Req>> {
+        let request = (self.map_request)(request);
+        let response = match self.timeout {
+            Some(timeout) => self.inner.send_timeout(request, timeout).await?,
+            None => self.inner.send(request).await?,
+        };
+        Ok((self.map_response)(response))
+    }
+
+    /// Convert each request with `f`, before any existing conversion
+    pub fn map_request<Outer, F>(self, f: F) -> MappedRequester<Outer, OuterResp, Req, Resp>
+    where
+        F: Fn(Outer) -> OuterReq + Send + Sync + 'static,
+    {
+        let map_request = self.map_request;
+        MappedRequester {
+            inner: self.inner,
+            map_request: Arc::new(move |request| map_request(f(request))),
+            map_response: self.map_response,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Convert each response with `f`, after any existing conversion
+    pub fn map_response<Outer, F>(self, f: F) -> MappedRequester<OuterReq, Outer, Req, Resp>
+    where
+        F: Fn(OuterResp) -> Outer + Send + Sync + 'static,
+    {
+        let map_response = self.map_response;
+        MappedRequester {
+            inner: self.inner,
+            map_request: self.map_request,
+            map_response: Arc::new(move |response| f(map_response(response))),
+            timeout: self.timeout,
+        }
+    }
+
+    /// Recover the underlying [`Requester`]
+    pub fn into_inner(self) -> Requester<Req, Resp> {
+        self.inner
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How a request sent by a [`Requester`](crate::Requester) with [`Requester::with_metrics`](crate::Requester::with_metrics) was measured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// The name the channel was created with by [`bounded_named`](crate::bounded_named) or [`unbounded_named`](crate::unbounded_named), if any
+    pub channel: Option<Arc<str>>,
+    /// How long from sending the request until the [`Responder`](crate::Responder) received it, including waiting for space on the channel.
+    /// `None` if it was never received
+    pub queue_wait: Option<Duration>,
+    /// How long the [`Responder`](crate::Responder) took to respond once it received the request.
+    /// `None` if it wasn't responded to
+    pub handler_time: Option<Duration>,
+    /// How long from sending the request until it was responded to, or given up on
+    pub round_trip: Duration,
+    /// What became of the request
+    pub outcome: Outcome,
+}
+
+/// What became of a request, see [`RequestMetrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The response was sent back
+    Responded,
+    /// The request was received, but dropped without a response reaching the [`Requester`](crate::Requester)
+    Ignored,
+    /// The request was evicted by [`Requester::send_overwrite`](crate::Requester::send_overwrite)
+    Shed,
+    /// The request was never received, e.g. because the [`Responder`](crate::Responder) was dropped
+    Closed,
+    /// The request never reached the channel, because it was full.
+    /// For example, [`Requester::try_send`](crate::Requester::try_send) on a full channel,
+    /// [`Overflow::Reject`](crate::Overflow::Reject), or giving up while waiting for space
+    Full,
+}
+
+pub(crate) type MetricsSink = Arc<dyn Fn(RequestMetrics) + Send + Sync>;
+
+/// Travels with a request, and reports its [`RequestMetrics`] when dropped
+pub(crate) struct Recorder {
+    sink: MetricsSink,
+    channel: Option<Arc<str>>,
+    sent: Instant,
+    received: Option<Instant>,
+    responded: Option<Instant>,
+    shed: bool,
+    /// Whether the last attempt to put the request on the channel found it full
+    full: bool,
+}
+
+impl Recorder {
+    pub(crate) fn new(sink: MetricsSink, channel: Option<Arc<str>>) -> Self {
+        Self {
+            sink,
+            channel,
+            sent: Instant::now(),
+            received: None,
+            responded: None,
+            shed: false,
+            full: false,
+        }
+    }
+
+    pub(crate) fn received(&mut self) {
+        self.received = Some(Instant::now())
+    }
+
+    pub(crate) fn responded(&mut self) {
+        self.responded = Some(Instant::now())
+    }
+
+    pub(crate) fn shed(&mut self) {
+        self.shed = true
+    }
+
+    pub(crate) fn found_full(&mut self, full: bool) {
+        self.full = full
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let outcome = match (self.received, self.responded) {
+            (_, Some(_)) => Outcome::Responded,
+            (Some(_), None) => Outcome::Ignored,
+            (None, None) if self.shed => Outcome::Shed,
+            (None, None) if self.full => Outcome::Full,
+            (None, None) => Outcome::Closed,
+        };
+        let finished = self.responded.unwrap_or_else(Instant::now);
+        (self.sink)(RequestMetrics {
+            channel: self.channel.take(),
+            queue_wait: self.received.map(|received| received - self.sent),
+            handler_time: self
+                .received
+                .zip(self.responded)
+                .map(|(received, responded)| responded - received),
+            round_trip: finished - self.sent,
+            outcome,
+        })
+    }
+}
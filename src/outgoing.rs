@@ -0,0 +1,436 @@
+use crate::{
+    backend::{ChannelBackend, Selected},
+    responses::Collector,
+    ReceivedRequest,
+};
+use async_channel as channel;
+use event_listener::{Event, EventListener};
+use futures::channel::oneshot;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{fence, AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// What's put on the underlying queue
+// Boxing the request would cost an allocation per send, just to shrink pings
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum Message<Req, Resp, M> {
+    Request(ReceivedRequest<Req, Resp, M>),
+    /// Answered by the [`Responder`](crate::Responder) as it takes it off the channel, so handlers never see it
+    Ping(oneshot::Sender<()>),
+}
+
+impl<Req, Resp, M> Message<Req, Resp, M> {
+    /// Answer a ping, or return the request
+    pub(crate) fn into_request(self) -> Option<ReceivedRequest<Req, Resp, M>> {
+        match self {
+            Self::Request(received) => Some(received),
+            Self::Ping(pong) => {
+                let _ = pong.send(());
+                None
+            }
+        }
+    }
+}
+
+/// What happens to a new request when the channel is full, set with [`bounded_with_policy`](crate::bounded_with_policy)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Wait for space on the channel.
+    /// This is how [`bounded`](crate::bounded) channels behave
+    Block,
+    /// Fail straight away, returning the new request, e.g. with [`SendRequestError::Full`](crate::SendRequestError::Full)
+    Reject,
+    /// Evict the oldest request on the channel to make space, which fails with [`SendRequestError::Shed`](crate::SendRequestError::Shed).
+    /// Waits if there's nothing to evict, because every request taking up space is being put on the channel
+    DropOldest,
+    /// Drop the new request, which fails with [`SendRequestError::Shed`](crate::SendRequestError::Shed)
+    DropNewest,
+}
+
+/// The sending end of the channel.
+/// The underlying queue is unbounded, and capacity is enforced here, so that it can change at runtime
+pub(crate) struct Outgoing<Req, Resp, M> {
+    /// Shared with the [`Responder`](crate::Responder)s
+    queue: Arc<Queue<Req, Resp, M>>,
+    limit: Arc<Limit>,
+    overflow: Overflow,
+    /// Responses to requests sent without waiting for them
+    pub(crate) collector: Collector<Resp>,
+}
+
+impl<Req, Resp, M> Outgoing<Req, Resp, M> {
+    pub(crate) fn new(
+        queue: Arc<Queue<Req, Resp, M>>,
+        limit: Arc<Limit>,
+        overflow: Overflow,
+    ) -> Self {
+        Self {
+            queue,
+            limit,
+            overflow,
+            collector: Collector::new(),
+        }
+    }
+
+    /// Take the oldest request off the channel, telling its requester it was shed.
+    /// Returns `false` if there was nothing to take
+    pub(crate) fn shed_oldest(&self) -> bool {
+        loop {
+            match self.queue.try_recv() {
+                // Dropping its slot makes space
+                Some(Message::Request(received)) => {
+                    received.unresponded.shed();
+                    return true;
+                }
+                // Pings don't take up space, but can't be answered without a responder
+                Some(Message::Ping(_)) => continue,
+                None => return false,
+            }
+        }
+    }
+
+    /// Put a ping on the channel, which doesn't take up any capacity.
+    /// Returns `false` if the channel is closed
+    pub(crate) fn ping(&self, pong: oneshot::Sender<()>) -> bool {
+        match self.limit.start_push() {
+            Some(pushing) => {
+                self.push(Message::Ping(pong), pushing);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Put a message on the queue, and wake a [`Responder`](crate::Responder) to take it off
+    fn push(&self, message: Message<Req, Resp, M>, pushing: Pushing<'_>) {
+        self.queue.send(message);
+        self.limit.arrived.notify(1);
+        drop(pushing);
+        // The last responder may have emptied the queue just before this was pushed, and nobody else will.
+        // Pairs with the fence in `Limit::remove_responder`, so one of us sees the other
+        fence(Ordering::SeqCst);
+        if self.limit.responders() == 0 {
+            while self.queue.try_recv().is_some() {}
+        }
+    }
+
+    /// Put the request on the channel, waiting while it's full
+    pub(crate) fn send(
+        &self,
+        received: ReceivedRequest<Req, Resp, M>,
+    ) -> Enqueue<'_, Req, Resp, M> {
+        Enqueue {
+            outgoing: self,
+            received: Some(received),
+            listener: None,
+        }
+    }
+
+    /// Put the request on the channel if there's space, or the [`Overflow`] policy makes it.
+    /// Under [`Overflow::DropNewest`], a new request which doesn't fit is shed, and this succeeds
+    // Mirrors async-channel, so the request comes back the same way
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn try_send(
+        &self,
+        mut received: ReceivedRequest<Req, Resp, M>,
+    ) -> Result<(), channel::TrySendError<ReceivedRequest<Req, Resp, M>>> {
+        received.unresponded.found_full(false);
+        let pushing = match self.limit.start_push() {
+            Some(pushing) => pushing,
+            None => return Err(channel::TrySendError::Closed(received)),
+        };
+        loop {
+            match self.limit.try_reserve() {
+                Some(slot) => {
+                    received.unresponded.slot = Some(slot);
+                    break;
+                }
+                None => match self.overflow {
+                    // Someone else may take the space first, so try again
+                    Overflow::DropOldest if self.shed_oldest() => continue,
+                    Overflow::DropNewest => {
+                        received.unresponded.shed();
+                        return Ok(());
+                    }
+                    _ => {
+                        received.unresponded.found_full(true);
+                        return Err(channel::TrySendError::Full(received));
+                    }
+                },
+            }
+        }
+        self.push(Message::Request(received), pushing);
+        Ok(())
+    }
+
+    /// Whether requests fail instead of waiting while the channel is full
+    pub(crate) fn rejects(&self) -> bool {
+        self.overflow == Overflow::Reject
+    }
+
+    /// Notified whenever there may be space on the channel, or it closes
+    pub(crate) fn listen(&self) -> EventListener {
+        self.limit.space.listen()
+    }
+
+    pub(crate) fn name(&self) -> Option<&Arc<str>> {
+        self.limit.name()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.limit.is_full()
+    }
+
+    pub(crate) fn capacity(&self) -> Option<usize> {
+        self.limit.capacity()
+    }
+
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        self.limit.set_capacity(capacity)
+    }
+
+    pub(crate) fn close(&self) -> bool {
+        self.limit.close()
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.limit.is_closed()
+    }
+
+    pub(crate) fn receiver_count(&self) -> usize {
+        self.limit.responders()
+    }
+}
+
+impl<Req, Resp, M> Drop for Outgoing<Req, Resp, M> {
+    fn drop(&mut self) {
+        // The last requester is gone
+        self.close();
+    }
+}
+
+/// The queue requests wait on, from the [`backend`](crate::backend) chosen by feature
+pub(crate) type Queue<Req, Resp, M> = Selected<Message<Req, Resp, M>>;
+
+/// Shared between both ends of the channel
+pub(crate) struct Limit {
+    /// `usize::MAX` if the channel is unbounded
+    capacity: AtomicUsize,
+    /// How many requests are on the channel, including those being put on it
+    queued: AtomicUsize,
+    /// Notified whenever there may be space on the channel, or it closes
+    space: Event,
+    /// Notified once the channel closes
+    closed: Event,
+    /// Set once the channel closes, after which nothing more is put on the queue
+    is_closed: AtomicBool,
+    /// How many messages are being put on the queue by senders which saw it open
+    pushing: AtomicUsize,
+    /// Notified whenever a message is put on the queue, or the channel closes
+    arrived: Event,
+    /// How many [`Responder`](crate::Responder)s share the channel.
+    /// Nothing can be received once the last one is dropped, so the queue is emptied
+    responders: AtomicUsize,
+    /// For telling channels apart in diagnostics
+    name: Option<Arc<str>>,
+}
+
+impl Limit {
+    pub(crate) fn new(capacity: Option<usize>, name: Option<Arc<str>>) -> Self {
+        Self {
+            capacity: AtomicUsize::new(capacity.unwrap_or(usize::MAX)),
+            queued: AtomicUsize::new(0),
+            space: Event::new(),
+            closed: Event::new(),
+            is_closed: AtomicBool::new(false),
+            pushing: AtomicUsize::new(0),
+            arrived: Event::new(),
+            responders: AtomicUsize::new(0),
+            name,
+        }
+    }
+
+    pub(crate) fn name(&self) -> Option<&Arc<str>> {
+        self.name.as_ref()
+    }
+
+    fn try_reserve(self: &Arc<Self>) -> Option<Slot> {
+        let capacity = self.capacity.load(Ordering::Acquire);
+        self.queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued < capacity).then(|| queued + 1)
+            })
+            .ok()
+            .map(|_| Slot {
+                limit: Arc::clone(self),
+            })
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.queued.load(Ordering::Acquire) >= self.capacity.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn capacity(&self) -> Option<usize> {
+        match self.capacity.load(Ordering::Acquire) {
+            usize::MAX => None,
+            capacity => Some(capacity),
+        }
+    }
+
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        assert!(capacity > 0, "capacity cannot be zero");
+        self.capacity.store(capacity, Ordering::Release);
+        // Growing may have made space for everyone waiting
+        self.wake_all();
+    }
+
+    /// Let every waiting sender check the channel again, e.g. because it closed
+    pub(crate) fn wake_all(&self) {
+        self.space.notify(usize::MAX);
+    }
+
+    /// Close the channel, and let everyone waiting on it know.
+    /// Returns `true` if this call closed it
+    pub(crate) fn close(&self) -> bool {
+        let closed = !self.is_closed.swap(true, Ordering::SeqCst);
+        self.wake_all();
+        self.closed.notify(usize::MAX);
+        self.arrived.notify(usize::MAX);
+        closed
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+
+    /// Count a message about to be put on the queue, or `None` if the channel is closed
+    fn start_push(&self) -> Option<Pushing<'_>> {
+        self.pushing.fetch_add(1, Ordering::SeqCst);
+        let pushing = Pushing { limit: self };
+        (!self.is_closed()).then_some(pushing)
+    }
+
+    /// Whether the channel is closed, and nothing more will be put on the queue.
+    /// A responder which finds the queue empty afterwards has received everything
+    pub(crate) fn is_finished(&self) -> bool {
+        self.is_closed() && self.pushing.load(Ordering::SeqCst) == 0
+    }
+
+    /// Notified once the channel closes
+    pub(crate) fn listen_closed(&self) -> EventListener {
+        self.closed.listen()
+    }
+
+    /// Notified whenever a message is put on the queue, or the channel closes
+    pub(crate) fn listen_arrived(&self) -> EventListener {
+        self.arrived.listen()
+    }
+
+    /// Count a new [`Responder`](crate::Responder)
+    pub(crate) fn add_responder(&self) {
+        self.responders.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Stop counting a dropped [`Responder`](crate::Responder).
+    /// Returns `true` if it was the last one
+    pub(crate) fn remove_responder(&self) -> bool {
+        let last = self.responders.fetch_sub(1, Ordering::AcqRel) == 1;
+        // Pairs with the fence in `Outgoing::push`, so anything pushed after the last responder empties the queue is emptied there
+        fence(Ordering::SeqCst);
+        last
+    }
+
+    pub(crate) fn responders(&self) -> usize {
+        self.responders.load(Ordering::Acquire)
+    }
+}
+
+/// A request's place on the channel, released when the [`Responder`](crate::Responder) takes it off
+pub(crate) struct Slot {
+    limit: Arc<Limit>,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.limit.queued.fetch_sub(1, Ordering::AcqRel);
+        self.limit.space.notify(1);
+    }
+}
+
+/// A message being put on the queue, which responders wait for even once the channel closes
+struct Pushing<'a> {
+    limit: &'a Limit,
+}
+
+impl<'a> Drop for Pushing<'a> {
+    fn drop(&mut self) {
+        self.limit.pushing.fetch_sub(1, Ordering::SeqCst);
+        // Responders which found the queue empty may be waiting for this
+        if self.limit.is_closed() {
+            self.limit.arrived.notify(usize::MAX);
+        }
+    }
+}
+
+/// Future returned by [`Outgoing::send`]
+pub(crate) struct Enqueue<'a, Req, Resp, M> {
+    outgoing: &'a Outgoing<Req, Resp, M>,
+    received: Option<ReceivedRequest<Req, Resp, M>>,
+    listener: Option<EventListener>,
+}
+
+// The request is moved into the channel by value, so polling never borrows it in place
+impl<'a, Req, Resp, M> Unpin for Enqueue<'a, Req, Resp, M> {}
+
+// Given up on before the request reached the channel
+impl<'a, Req, Resp, M> Drop for Enqueue<'a, Req, Resp, M> {
+    fn drop(&mut self) {
+        if let Some(received) = &mut self.received {
+            received.unresponded.refuse()
+        }
+    }
+}
+
+impl<'a, Req, Resp, M> Future for Enqueue<'a, Req, Resp, M> {
+    // Full if the channel rejects requests while full
+    type Output = Result<(), channel::TrySendError<ReceivedRequest<Req, Resp, M>>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            let received = self
+                .received
+                .take()
+                .expect("Enqueue polled after completion");
+            match self.outgoing.try_send(received) {
+                Ok(()) => return Poll::Ready(Ok(())),
+                Err(channel::TrySendError::Full(received)) if self.outgoing.rejects() => {
+                    return Poll::Ready(Err(channel::TrySendError::Full(received)))
+                }
+                Err(channel::TrySendError::Full(received)) => self.received = Some(received),
+                Err(closed) => return Poll::Ready(Err(closed)),
+            }
+            match &mut self.listener {
+                // Space may have been made before we started listening, so check again
+                None => self.listener = Some(self.outgoing.limit.space.listen()),
+                Some(listener) => {
+                    futures::ready!(Pin::new(listener).poll(cx));
+                    self.listener = None;
+                }
+            }
+        }
+    }
+}
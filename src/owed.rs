@@ -0,0 +1,111 @@
+use crate::{lock, ReceivedRequest, Responder};
+use futures::Stream;
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
+};
+
+/// The requests yielded by an [`OwedStream`] which haven't been responded to yet.
+/// Only metadata is kept, so requests aren't kept alive any longer than their handler keeps them.
+#[derive(Clone, Default)]
+pub struct OwedResponses {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    owed: BTreeMap<u64, Instant>,
+}
+
+/// A response which is still owed, listed by [`OwedResponses::list`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwedResponse {
+    /// Counts up from zero, in the order the requests were yielded
+    pub id: u64,
+    /// When the request was yielded
+    pub received_at: Instant,
+}
+
+impl OwedResponses {
+    /// Every response still owed, oldest first
+    pub fn list(&self) -> Vec<OwedResponse> {
+        lock(&self.inner)
+            .owed
+            .iter()
+            .map(|(id, received_at)| OwedResponse {
+                id: *id,
+                received_at: *received_at,
+            })
+            .collect()
+    }
+
+    /// How many responses are still owed
+    pub fn count(&self) -> usize {
+        lock(&self.inner).owed.len()
+    }
+
+    fn track(&self) -> OwedGuard {
+        let mut inner = lock(&self.inner);
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.owed.insert(id, Instant::now());
+        OwedGuard {
+            owed: self.clone(),
+            id,
+        }
+    }
+}
+
+/// Removes its entry from [`OwedResponses`] when the handle is responded to, or dropped
+struct OwedGuard {
+    owed: OwedResponses,
+    id: u64,
+}
+
+impl Drop for OwedGuard {
+    fn drop(&mut self) {
+        lock(&self.owed.inner).owed.remove(&self.id);
+    }
+}
+
+/// Stream returned by [`ResponderExt::into_owed_stream`](crate::ResponderExt::into_owed_stream)
+#[must_use = "streams do nothing unless polled"]
+pub struct OwedStream<Req, Resp, M = ()> {
+    responder: Responder<Req, Resp, M>,
+    owed: OwedResponses,
+}
+
+impl<Req, Resp, M> OwedStream<Req, Resp, M> {
+    pub(crate) fn new(responder: Responder<Req, Resp, M>) -> Self {
+        Self {
+            responder,
+            owed: OwedResponses::default(),
+        }
+    }
+
+    /// The responses owed for requests yielded so far.
+    /// This is a shared handle, so it can be inspected while the stream is in use elsewhere
+    pub fn owed(&self) -> OwedResponses {
+        self.owed.clone()
+    }
+}
+
+impl<Req, Resp, M> Stream for OwedStream<Req, Resp, M> {
+    type Item = ReceivedRequest<Req, Resp, M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.responder).poll_next(cx).map(|received| {
+            received.map(|mut received| {
+                received
+                    .unresponded
+                    .trackers
+                    .push(Box::new(self.owed.track()));
+                received
+            })
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use event_listener::Event;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Counts requests which have been received, but not yet responded to.
+/// Share one between the [`Responder`](crate::Responder)s whose concurrency you want to limit,
+/// with [`ResponderExt::recv_limited`](crate::ResponderExt::recv_limited).
+#[derive(Clone, Default)]
+pub struct PendingResponses {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    count: AtomicUsize,
+    // Notified whenever a pending response completes
+    completed: Event,
+}
+
+impl PendingResponses {
+    /// How many requests are currently waiting for a response
+    pub fn count(&self) -> usize {
+        self.inner.count.load(Ordering::Acquire)
+    }
+
+    /// Wait until fewer than `max` responses are pending, and reserve a slot.
+    pub(crate) async fn reserve(&self, max: usize) -> PendingGuard {
+        loop {
+            if let Some(guard) = self.try_reserve(max) {
+                return guard;
+            }
+            let listener = self.inner.completed.listen();
+            // A response may have completed before we started listening
+            if let Some(guard) = self.try_reserve(max) {
+                return guard;
+            }
+            listener.await;
+        }
+    }
+
+    fn try_reserve(&self, max: usize) -> Option<PendingGuard> {
+        self.inner
+            .count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < max).then(|| count + 1)
+            })
+            .ok()
+            .map(|_| PendingGuard {
+                inner: Arc::clone(&self.inner),
+            })
+    }
+}
+
+/// Releases its slot in [`PendingResponses`] when dropped
+pub(crate) struct PendingGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        self.inner.count.fetch_sub(1, Ordering::AcqRel);
+        self.inner.completed.notify(usize::MAX);
+    }
+}
@@ -0,0 +1,115 @@
+//! Spread requests over several independent [`Responder`](crate::Responder)s.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, pool::{RequesterPool, Strategy}};
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (first, first_responder) = bounded::<&str, usize>(1);
+//! let (second, second_responder) = bounded::<&str, usize>(1);
+//! let pool = RequesterPool::new(vec![first, second], Strategy::RoundRobin);
+//! let (response, _) = join!(pool.send("hello"), async {
+//!     let request = first_responder.recv().await.unwrap();
+//!     let len = request.len();
+//!     request.respond(len).unwrap()
+//! });
+//! assert!(response.unwrap() == 5)
+//! # })
+//! ```
+
+use crate::{Requester, SendRequestError};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// How a [`RequesterPool`] picks a backend for each request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Each backend in turn
+    RoundRobin,
+    /// The backend with the fewest requests waiting on its channel
+    LeastLoaded,
+    /// A backend at random
+    Random,
+}
+
+/// A set of [`Requester`]s, each to an independent [`Responder`](crate::Responder), which are used as one
+pub struct RequesterPool<Req, Resp> {
+    backends: Vec<Requester<Req, Resp>>,
+    strategy: Strategy,
+    retry_closed: bool,
+    counter: AtomicUsize,
+    random: RandomState,
+}
+
+impl<Req, Resp> RequesterPool<Req, Resp> {
+    /// Create a pool over `backends`, which picks between them according to `strategy`
+    pub fn new(
+        backends: impl IntoIterator<Item = Requester<Req, Resp>>,
+        strategy: Strategy,
+    ) -> Self {
+        Self {
+            backends: backends.into_iter().collect(),
+            strategy,
+            retry_closed: false,
+            counter: AtomicUsize::new(0),
+            random: RandomState::new(),
+        }
+    }
+
+    /// If a backend's [`Responder`](crate::Responder) was dropped, try the request on the other backends in turn.
+    /// Off by default
+    pub fn retry_closed(mut self, retry: bool) -> Self {
+        self.retry_closed = retry;
+        self
+    }
+
+    /// The [`Requester`]s in this pool
+    pub fn backends(&self) -> &[Requester<Req, Resp>] {
+        &self.backends
+    }
+
+    /// Make a request to one of the backends.
+    /// `await` the result to receive the response.
+    /// Fails with [`SendRequestError::Closed`] if the pool is empty.
+    pub async fn send(&self, mut request: Req) -> Result<Resp, SendRequestError<Req>> {
+        let start = match self.pick() {
+            Some(start) => start,
+            None => return Err(SendRequestError::Closed(request)),
+        };
+        let attempts = if self.retry_closed {
+            self.backends.len()
+        } else {
+            1
+        };
+        for offset in 0..attempts {
+            let backend = &self.backends[(start + offset) % self.backends.len()];
+            match backend.send(request).await {
+                Err(SendRequestError::Closed(returned)) => request = returned,
+                result => return result,
+            }
+        }
+        Err(SendRequestError::Closed(request))
+    }
+
+    fn pick(&self) -> Option<usize> {
+        if self.backends.is_empty() {
+            return None;
+        }
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+        Some(match self.strategy {
+            Strategy::RoundRobin => count % self.backends.len(),
+            Strategy::LeastLoaded => (0..self.backends.len())
+                // Break ties in turn, so that idle backends share the load
+                .map(|offset| (count + offset) % self.backends.len())
+                .min_by_key(|index| self.backends[*index].len())
+                .expect("pool is not empty"),
+            Strategy::Random => {
+                let mut hasher = self.random.build_hasher();
+                hasher.write_usize(count);
+                hasher.finish() as usize % self.backends.len()
+            }
+        })
+    }
+}
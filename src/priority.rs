@@ -0,0 +1,389 @@
+//! A channel whose [`PriorityResponder`] receives the highest priority request first.
+//!
+//! Requests may age, so that a low priority request's effective priority rises the longer it waits,
+//! and it can't be starved by a stream of high priority requests.
+//! See [`PriorityConfig`].
+//!
+//! For priorities which aren't numbers, like an `enum` of command kinds,
+//! [`bounded_priority`] and [`unbounded_priority`] order requests by any [`Ord`] type instead, without aging.
+//!
+//! ```
+//! use bidirectional_channel::priority::{unbounded, PriorityConfig};
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = unbounded::<&str, &str>(PriorityConfig::default());
+//! let requests = async { join!(requester.send("routine", 0.0), requester.send("urgent", 10.0)) };
+//! let responses = async {
+//!     let first = responder.recv().await.unwrap();
+//!     assert!(*first == "urgent");
+//!     first.respond("done").unwrap();
+//!     let second = responder.recv().await.unwrap();
+//!     second.respond("done").unwrap();
+//! };
+//! join!(requests, responses);
+//! # })
+//! ```
+
+use crate::{lock, ReceivedRequest, RecvError, Requester, Response, SendRequestError};
+use event_listener::Event;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// How a priority channel orders its requests
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PriorityConfig {
+    /// How much a waiting request's priority increases per second.
+    /// Effective priority is `priority + seconds_waited * aging_rate`.
+    /// Zero (the default) gives strict priority order.
+    pub aging_rate: f64,
+}
+
+/// The effective priority of a request on a channel created by [`bounded`] or [`unbounded`], with aging applied.
+/// Ordered by [`f64::total_cmp`]
+#[derive(Debug, Clone, Copy)]
+pub struct Aged(f64);
+
+impl Ord for Aged {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+impl PartialOrd for Aged {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Aged {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Aged {}
+
+struct Shared<Req, Resp, P> {
+    state: Mutex<State<Req, Resp, P>>,
+    capacity: usize,
+    config: PriorityConfig,
+    /// Ages are measured from here
+    epoch: Instant,
+    /// Notified when a request is pushed, or the last requester is dropped
+    pushed: Event,
+    /// Notified when a request is popped, or the last responder is dropped
+    popped: Event,
+}
+
+struct State<Req, Resp, P> {
+    queue: BinaryHeap<Entry<Req, Resp, P>>,
+    /// Breaks ties in FIFO order
+    sequence: u64,
+    requesters: usize,
+    responders: usize,
+}
+
+struct Entry<Req, Resp, P> {
+    /// For an [`Aged`] priority, every request ages at the same rate,
+    /// so ordering by `priority - enqueued_at * aging_rate` is the same as by effective priority
+    priority: P,
+    sequence: u64,
+    request: ReceivedRequest<Req, Resp>,
+}
+
+impl<Req, Resp, P: Ord> Ord for Entry<Req, Resp, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+impl<Req, Resp, P: Ord> PartialOrd for Entry<Req, Resp, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<Req, Resp, P: Ord> PartialEq for Entry<Req, Resp, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<Req, Resp, P: Ord> Eq for Entry<Req, Resp, P> {}
+
+enum Push<Req, Resp> {
+    Pushed,
+    Full(ReceivedRequest<Req, Resp>),
+    Closed(ReceivedRequest<Req, Resp>),
+}
+
+/// Sends requests with a priority
+pub struct PriorityRequester<Req, Resp, P = Aged> {
+    shared: Arc<Shared<Req, Resp, P>>,
+}
+
+impl<Req, Resp> PriorityRequester<Req, Resp> {
+    /// Make a request with `priority` - higher is received sooner.
+    /// `await` the result to receive the response.
+    /// Fails if the [`PriorityResponder`] was dropped, and returns your request back
+    pub async fn send(&self, request: Req, priority: f64) -> Result<Resp, SendRequestError<Req>> {
+        let age = self.shared.epoch.elapsed().as_secs_f64();
+        let aged = Aged(priority - age * self.shared.config.aging_rate);
+        self.send_priority(request, aged).await
+    }
+}
+
+impl<Req, Resp, P: Ord> PriorityRequester<Req, Resp, P> {
+    /// Make a request with `priority` - greater is received sooner, and equal priorities are received in the order they were sent.
+    /// `await` the result to receive the response.
+    /// Fails if the [`PriorityResponder`] was dropped, and returns your request back
+    pub async fn send_priority(
+        &self,
+        request: Req,
+        priority: P,
+    ) -> Result<Resp, SendRequestError<Req>> {
+        let (received, response_receiver) = Requester::prepare(request, (), |_| ());
+        self.enqueue(received, priority)
+            .await
+            .map_err(SendRequestError::Closed)?;
+        Ok(Response::new(response_receiver).await?)
+    }
+
+    /// Fails if the channel is closed, returning the request
+    async fn enqueue(
+        &self,
+        mut received: ReceivedRequest<Req, Resp>,
+        priority: P,
+    ) -> Result<(), Req> {
+        let mut priority = Some(priority);
+        loop {
+            received = match self.try_push(received, &mut priority) {
+                Push::Pushed => return Ok(()),
+                Push::Closed(received) => return Err(received.into_refused()),
+                Push::Full(received) => received,
+            };
+            let listener = self.shared.popped.listen();
+            // A request may have been popped before we started listening
+            received = match self.try_push(received, &mut priority) {
+                Push::Pushed => return Ok(()),
+                Push::Closed(received) => return Err(received.into_refused()),
+                Push::Full(received) => received,
+            };
+            listener.await;
+        }
+    }
+
+    /// Takes the priority once the request is pushed
+    fn try_push(
+        &self,
+        received: ReceivedRequest<Req, Resp>,
+        priority: &mut Option<P>,
+    ) -> Push<Req, Resp> {
+        let mut state = lock(&self.shared.state);
+        if state.responders == 0 {
+            return Push::Closed(received);
+        }
+        if state.queue.len() >= self.shared.capacity {
+            return Push::Full(received);
+        }
+        let sequence = state.sequence;
+        state.sequence += 1;
+        state.queue.push(Entry {
+            priority: priority.take().expect("only pushed once"),
+            sequence,
+            request: received,
+        });
+        drop(state);
+        self.shared.pushed.notify(1);
+        Push::Pushed
+    }
+}
+
+impl<Req, Resp, P> PriorityRequester<Req, Resp, P> {
+    /// How many requests are waiting on the channel
+    pub fn len(&self) -> usize {
+        lock(&self.shared.state).queue.len()
+    }
+
+    /// Whether there are no requests waiting on the channel
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<Req, Resp, P> Clone for PriorityRequester<Req, Resp, P> {
+    fn clone(&self) -> Self {
+        lock(&self.shared.state).requesters += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Req, Resp, P> Drop for PriorityRequester<Req, Resp, P> {
+    fn drop(&mut self) {
+        let mut state = lock(&self.shared.state);
+        state.requesters -= 1;
+        if state.requesters == 0 {
+            drop(state);
+            self.shared.pushed.notify(usize::MAX);
+        }
+    }
+}
+
+/// Receives the highest priority request first
+pub struct PriorityResponder<Req, Resp, P = Aged> {
+    shared: Arc<Shared<Req, Resp, P>>,
+}
+
+impl<Req, Resp, P: Ord> PriorityResponder<Req, Resp, P> {
+    /// Receive the request with the highest effective priority.
+    /// Fails if every [`PriorityRequester`] was dropped, and the channel is empty
+    pub async fn recv(&self) -> Result<ReceivedRequest<Req, Resp>, RecvError> {
+        loop {
+            if let Some(received) = self.try_pop()? {
+                return Ok(received);
+            }
+            let listener = self.shared.pushed.listen();
+            // A request may have been pushed before we started listening
+            if let Some(received) = self.try_pop()? {
+                return Ok(received);
+            }
+            listener.await;
+        }
+    }
+
+    fn try_pop(&self) -> Result<Option<ReceivedRequest<Req, Resp>>, RecvError> {
+        let mut state = lock(&self.shared.state);
+        match state.queue.pop() {
+            Some(entry) => {
+                drop(state);
+                self.shared.popped.notify(1);
+                Ok(Some(entry.request))
+            }
+            None if state.requesters == 0 => Err(RecvError),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<Req, Resp, P> PriorityResponder<Req, Resp, P> {
+    /// How many requests are waiting on the channel
+    pub fn len(&self) -> usize {
+        lock(&self.shared.state).queue.len()
+    }
+
+    /// Whether there are no requests waiting on the channel
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<Req, Resp, P> Clone for PriorityResponder<Req, Resp, P> {
+    fn clone(&self) -> Self {
+        lock(&self.shared.state).responders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Req, Resp, P> Drop for PriorityResponder<Req, Resp, P> {
+    fn drop(&mut self) {
+        let mut state = lock(&self.shared.state);
+        state.responders -= 1;
+        if state.responders == 0 {
+            // Nobody will respond to these, so let their requesters know
+            let abandoned = std::mem::take(&mut state.queue);
+            drop(state);
+            drop(abandoned);
+            self.shared.popped.notify(usize::MAX);
+        }
+    }
+}
+
+/// Create a bounded [`PriorityRequester`]-[`PriorityResponder`] pair.
+/// See [`crate::bounded`]
+pub fn bounded<Req, Resp>(
+    capacity: usize,
+    config: PriorityConfig,
+) -> (PriorityRequester<Req, Resp>, PriorityResponder<Req, Resp>) {
+    pair(capacity, config)
+}
+
+/// Create an unbounded [`PriorityRequester`]-[`PriorityResponder`] pair.
+/// See [`crate::unbounded`]
+pub fn unbounded<Req, Resp>(
+    config: PriorityConfig,
+) -> (PriorityRequester<Req, Resp>, PriorityResponder<Req, Resp>) {
+    bounded(usize::MAX, config)
+}
+
+/// Create a bounded [`PriorityRequester`]-[`PriorityResponder`] pair, whose requests are sent with [`PriorityRequester::send_priority`].
+/// Requests are received greatest `P` first, and equal priorities in the order they were sent
+///
+/// ```
+/// use bidirectional_channel::priority::bounded_priority;
+/// # use futures::join;
+/// # async_std::task::block_on(async {
+/// #[derive(PartialEq, Eq, PartialOrd, Ord)]
+/// enum Kind {
+///     Data,
+///     Cancel,
+/// }
+/// let (requester, responder) = bounded_priority::<&str, (), Kind>(2);
+/// let requests = async { join!(requester.send_priority("write", Kind::Data), requester.send_priority("stop", Kind::Cancel)) };
+/// let responses = async {
+///     let first = responder.recv().await.unwrap();
+///     assert!(*first == "stop");
+///     first.respond(()).unwrap();
+///     responder.recv().await.unwrap().respond(()).unwrap();
+/// };
+/// join!(requests, responses);
+/// # })
+/// ```
+pub fn bounded_priority<Req, Resp, P: Ord>(
+    capacity: usize,
+) -> (
+    PriorityRequester<Req, Resp, P>,
+    PriorityResponder<Req, Resp, P>,
+) {
+    pair(capacity, PriorityConfig::default())
+}
+
+/// Create an unbounded [`PriorityRequester`]-[`PriorityResponder`] pair, whose requests are sent with [`PriorityRequester::send_priority`].
+/// See [`bounded_priority`]
+pub fn unbounded_priority<Req, Resp, P: Ord>() -> (
+    PriorityRequester<Req, Resp, P>,
+    PriorityResponder<Req, Resp, P>,
+) {
+    bounded_priority(usize::MAX)
+}
+
+fn pair<Req, Resp, P>(
+    capacity: usize,
+    config: PriorityConfig,
+) -> (
+    PriorityRequester<Req, Resp, P>,
+    PriorityResponder<Req, Resp, P>,
+) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: BinaryHeap::new(),
+            sequence: 0,
+            requesters: 1,
+            responders: 1,
+        }),
+        capacity,
+        config,
+        epoch: Instant::now(),
+        pushed: Event::new(),
+        popped: Event::new(),
+    });
+    (
+        PriorityRequester {
+            shared: shared.clone(),
+        },
+        PriorityResponder { shared },
+    )
+}
@@ -0,0 +1,918 @@
+use crate::{
+    backend::ChannelBackend,
+    idempotency::{IdempotencyConfig, Idempotent},
+    lock,
+    outgoing::{Limit, Message, Outgoing, Queue},
+    InFlightCount, OwedStream, PendingResponses, ReceivedRequest, RecvError, TryRecvError,
+};
+use event_listener::EventListener;
+use futures::{
+    future::poll_fn,
+    stream::{select_all, FusedStream, SelectAll},
+    Stream, StreamExt,
+};
+use futures_timer::Delay;
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Represents the receiver for the request-response exchange.
+/// Yields a [`ReceivedRequest`], which should be used to respond to the [`Requester`](crate::Requester).
+/// This is also a [`Stream`] of requests, which ends once every [`Requester`](crate::Requester) was dropped, and the channel is empty.
+/// The stream is a [`FusedStream`]: once it ends, the channel is done and it yields `None` forever, so it can be used in `select!` without [`fuse`](futures::StreamExt::fuse).
+///
+/// Clones receive from the same channel, so a pool of workers can share one: each request is received by exactly one of them.
+pub struct Responder<Req, Resp, M = ()> {
+    /// Shared with the [`Requester`](crate::Requester)s
+    incoming: Arc<Queue<Req, Resp, M>>,
+    /// Shared with the [`Requester`](crate::Requester)s
+    in_flight: Arc<InFlightCount>,
+    /// Shared by every [`Requester`](crate::Requester), so they can be counted
+    outgoing: Weak<Outgoing<Req, Resp, M>>,
+    /// Shared with the [`Requester`](crate::Requester)s, to enforce the capacity
+    limit: Arc<Limit>,
+    /// Taken off the channel by [`Responder::peek`], and received before anything else.
+    /// Not shared between clones
+    peeked: Mutex<Option<ReceivedRequest<Req, Resp, M>>>,
+    /// Waiting for a message to arrive, while this is polled as a [`Stream`]
+    listener: Option<EventListener>,
+}
+
+// Requests and responses needn't be `Clone` for the handle to be
+impl<Req, Resp, M> Clone for Responder<Req, Resp, M> {
+    fn clone(&self) -> Self {
+        self.limit.add_responder();
+        Self {
+            incoming: self.incoming.clone(),
+            in_flight: self.in_flight.clone(),
+            outgoing: self.outgoing.clone(),
+            limit: self.limit.clone(),
+            peeked: Mutex::new(None),
+            listener: None,
+        }
+    }
+}
+
+// The peeked request sits behind a mutex, and is only ever moved out whole
+impl<Req, Resp, M> Unpin for Responder<Req, Resp, M> {}
+
+impl<Req, Resp, M> Drop for Responder<Req, Resp, M> {
+    fn drop(&mut self) {
+        if self.limit.remove_responder() {
+            // This is the last one, so the channel is closing anyway
+            self.close();
+            // Nobody can handle what's left, so let its requesters know
+            while self.incoming.try_recv().is_some() {}
+        }
+    }
+}
+
+impl<Req, Resp, M> Debug for Responder<Req, Resp, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("Responder");
+        // Only named channels show their name
+        if let Some(name) = self.name() {
+            f.field("name", &name);
+        }
+        f.field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .field("requesters_alive", &!self.is_closed())
+            .finish()
+    }
+}
+
+impl<Req, Resp, M> Responder<Req, Resp, M> {
+    pub(crate) fn new(
+        incoming: Arc<Queue<Req, Resp, M>>,
+        in_flight: Arc<InFlightCount>,
+        outgoing: Weak<Outgoing<Req, Resp, M>>,
+        limit: Arc<Limit>,
+    ) -> Self {
+        limit.add_responder();
+        Self {
+            incoming,
+            in_flight,
+            outgoing,
+            limit,
+            peeked: Mutex::new(None),
+            listener: None,
+        }
+    }
+
+    /// Receive a request, waiting while the channel is empty.
+    /// Fails if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    pub fn recv(&self) -> Recv<'_, Req, Resp, M> {
+        Recv {
+            responder: self,
+            listener: None,
+        }
+    }
+
+    /// Receive a request, blocking the current thread while the channel is empty.
+    /// See [`Responder::recv`].
+    /// **Do not call this from within an async context**: it will block the executor thread until it completes.
+    #[cfg(feature = "blocking")]
+    pub fn recv_blocking(&self) -> Result<ReceivedRequest<Req, Resp, M>, RecvError> {
+        futures::executor::block_on(self.recv())
+    }
+
+    /// Receive a request from a hand-written [`Future::poll`], registering `cx` to be woken when one arrives.
+    /// Fails if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    pub fn poll_recv(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<ReceivedRequest<Req, Resp, M>, RecvError>> {
+        Pin::new(self)
+            .poll_next(cx)
+            .map(|received| received.ok_or(RecvError))
+    }
+
+    /// Receive a request if there's one on the channel, without waiting
+    pub fn try_recv(&self) -> Result<ReceivedRequest<Req, Resp, M>, TryRecvError> {
+        if let Some(peeked) = self.take_peeked() {
+            return Ok(peeked);
+        }
+        loop {
+            if let Some(received) = try_pop(&self.incoming, &self.limit)?.into_request() {
+                return Ok(picked_up(received, &self.incoming));
+            }
+        }
+    }
+
+    /// Receive up to `max` requests at once, e.g. to handle them in one database transaction.
+    /// Waits while the channel is empty, then takes any others already waiting, without waiting for more.
+    /// Each request is still responded to separately.
+    /// Returns an empty [`Vec`] if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    ///
+    /// # Panics
+    /// If `max` is zero
+    pub async fn recv_many(&self, max: usize) -> Vec<ReceivedRequest<Req, Resp, M>> {
+        assert!(max > 0, "max cannot be zero");
+        let first = match self.recv().await {
+            Ok(first) => first,
+            Err(RecvError) => return Vec::new(),
+        };
+        let mut batch = Vec::with_capacity(max.min(self.len() + 1));
+        batch.push(first);
+        batch.extend(std::iter::from_fn(|| self.try_recv().ok()).take(max - 1));
+        batch
+    }
+
+    /// Respond to each request on the channel with the output of `f`, one at a time, until every [`Requester`](crate::Requester) is dropped and the channel is empty.
+    /// If a [`Requester`](crate::Requester) stops waiting for its response, the response is discarded
+    pub async fn serve_fn<F, Fut>(self, mut f: F)
+    where
+        F: FnMut(Req) -> Fut,
+        Fut: Future<Output = Resp>,
+    {
+        while let Ok(received) = self.recv().await {
+            let (request, unresponded) = received.split();
+            let _ = unresponded.respond(f(request).await);
+        }
+    }
+
+    /// Like [`Responder::serve_fn`], but handles up to `limit` requests at once.
+    /// Responses are sent as they complete, so may be out of order.
+    /// Requests are only taken off the channel while there's room, so a busy handler applies backpressure to the channel
+    ///
+    /// # Panics
+    /// If `limit` is zero
+    pub async fn serve_fn_concurrent<F, Fut>(self, limit: usize, mut f: F)
+    where
+        F: FnMut(Req) -> Fut,
+        Fut: Future<Output = Resp>,
+    {
+        assert!(limit > 0, "limit cannot be zero");
+        self.map(|received| {
+            let (request, unresponded) = received.split();
+            let response = f(request);
+            async move {
+                let _ = unresponded.respond(response.await);
+            }
+        })
+        .buffer_unordered(limit)
+        .for_each(|()| async {})
+        .await
+    }
+
+    /// Look at the next request without receiving it, if one is waiting.
+    /// The request is taken off the channel and held by this [`Responder`] (not its clones), so the next receive returns it
+    pub fn peek(&mut self) -> Option<&Req> {
+        let incoming = &self.incoming;
+        let peeked = self.peeked.get_mut().unwrap_or_else(|e| e.into_inner());
+        if peeked.is_none() {
+            *peeked = std::iter::from_fn(|| incoming.try_recv())
+                .find_map(Message::into_request)
+                .map(|received| picked_up(received, incoming));
+        }
+        peeked.as_ref().map(|received| &received.request)
+    }
+
+    fn take_peeked(&self) -> Option<ReceivedRequest<Req, Resp, M>> {
+        lock(&self.peeked).take()
+    }
+
+    fn has_peeked(&self) -> bool {
+        lock(&self.peeked).is_some()
+    }
+
+    /// The name the channel was created with by [`bounded_named`](crate::bounded_named) or [`unbounded_named`](crate::unbounded_named), if any
+    pub fn name(&self) -> Option<&str> {
+        self.limit.name().map(|name| &**name)
+    }
+
+    /// How many requests are waiting on the channel, including a [peeked](Responder::peek) request
+    pub fn len(&self) -> usize {
+        self.incoming.len() + usize::from(self.has_peeked())
+    }
+
+    /// Whether there are no requests waiting on the channel, or [peeked](Responder::peek)
+    pub fn is_empty(&self) -> bool {
+        self.incoming.is_empty() && !self.has_peeked()
+    }
+
+    /// Whether the channel is full
+    pub fn is_full(&self) -> bool {
+        self.limit.is_full()
+    }
+
+    /// How many requests the channel holds, or `None` if it's unbounded
+    pub fn capacity(&self) -> Option<usize> {
+        self.limit.capacity()
+    }
+
+    /// Change how many requests the channel holds.
+    /// See [`Requester::set_capacity`](crate::Requester::set_capacity)
+    ///
+    /// # Panics
+    /// If `capacity` is zero
+    pub fn set_capacity(&self, capacity: usize) {
+        self.limit.set_capacity(capacity)
+    }
+
+    /// Close the channel, so [`Requester`](crate::Requester)s can't send any more requests.
+    /// Requests already on the channel may still be received.
+    /// Returns `true` if this call closed the channel
+    pub fn close(&self) -> bool {
+        // Requesters waiting for space see the channel close
+        self.limit.close()
+    }
+
+    /// Wait until the channel is closed, because every [`Requester`](crate::Requester) was dropped, or it was closed explicitly.
+    /// [`WeakRequester`](crate::WeakRequester)s don't keep it open.
+    /// Requests already on the channel may still be received afterwards.
+    /// This is useful in a `select!` loop, e.g. alongside a timer to shut down when idle
+    pub async fn closed(&self) {
+        loop {
+            if self.is_closed() {
+                return;
+            }
+            let listener = self.limit.listen_closed();
+            // The channel may have closed before we started listening
+            if self.is_closed() {
+                return;
+            }
+            listener.await;
+        }
+    }
+
+    /// Close the channel, and take every request left on it, e.g. to respond with a shutdown error or re-route them.
+    /// [`Requester`](crate::Requester)s waiting for space on the channel see [`SendRequestError::Closed`](crate::SendRequestError::Closed).
+    /// Other [`Responder`]s on this channel can't receive any more requests
+    pub fn drain(self) -> Vec<ReceivedRequest<Req, Resp, M>> {
+        self.close();
+        std::iter::from_fn(|| self.try_recv().ok()).collect()
+    }
+
+    /// How many requests have been sent, but not yet responded to or dropped.
+    /// See [`Requester::in_flight`](crate::Requester::in_flight)
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.get()
+    }
+
+    /// How many [`Responder`]s share this channel, including this one
+    pub fn receiver_count(&self) -> usize {
+        self.limit.responders()
+    }
+
+    /// How many [`Requester`](crate::Requester)s share this channel.
+    /// [`WeakRequester`](crate::WeakRequester)s aren't counted
+    pub fn sender_count(&self) -> usize {
+        self.outgoing.strong_count()
+    }
+
+    /// Whether the channel is closed
+    pub fn is_closed(&self) -> bool {
+        self.limit.is_closed()
+    }
+}
+
+impl<Req, Resp, M> Stream for Responder<Req, Resp, M> {
+    type Item = ReceivedRequest<Req, Resp, M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(peeked) = self.take_peeked() {
+            return Poll::Ready(Some(peeked));
+        }
+        let this = &mut *self;
+        loop {
+            match futures::ready!(poll_pop(
+                &this.incoming,
+                &this.limit,
+                &mut this.listener,
+                cx
+            )) {
+                Some(message) => {
+                    if let Some(received) = message.into_request() {
+                        return Poll::Ready(Some(picked_up(received, &this.incoming)));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<Req, Resp, M> FusedStream for Responder<Req, Resp, M> {
+    fn is_terminated(&self) -> bool {
+        self.limit.is_finished() && self.incoming.is_empty() && !self.has_peeked()
+    }
+}
+
+/// Future returned by [`Responder::recv`]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Recv<'a, Req, Resp, M = ()> {
+    /// Its [peeked](Responder::peek) request is returned first, without waiting on the channel.
+    /// That's only taken once this is polled, so dropping this beforehand leaves it in place
+    responder: &'a Responder<Req, Resp, M>,
+    listener: Option<EventListener>,
+}
+
+impl<'a, Req, Resp, M> Future for Recv<'a, Req, Resp, M> {
+    type Output = Result<ReceivedRequest<Req, Resp, M>, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(peeked) = self.responder.take_peeked() {
+            return Poll::Ready(Ok(peeked));
+        }
+        let Self {
+            responder,
+            listener,
+        } = &mut *self;
+        loop {
+            match futures::ready!(poll_pop(
+                &responder.incoming,
+                &responder.limit,
+                listener,
+                cx
+            )) {
+                Some(message) => {
+                    if let Some(received) = message.into_request() {
+                        return Poll::Ready(Ok(picked_up(received, &responder.incoming)));
+                    }
+                    // Answered a ping, so wait for the next message
+                }
+                None => return Poll::Ready(Err(RecvError)),
+            }
+        }
+    }
+}
+
+/// Receive a request from whichever of `responders` has one first, returning its index alongside the request.
+/// Responders are checked from a random starting point each time, so that later ones aren't starved.
+/// Fails if every [`Requester`](crate::Requester) of every responder was dropped, and their channels are empty
+///
+/// ```
+/// use bidirectional_channel::{bounded, select_any};
+/// # async_std::task::block_on(async {
+/// let (_first, first_responder) = bounded::<&str, usize>(1);
+/// let (second, second_responder) = bounded::<&str, usize>(1);
+/// let mut responders = [first_responder, second_responder];
+/// let response = second.request("hello").await.unwrap();
+/// let (index, request) = select_any(&mut responders).await.unwrap();
+/// assert!(index == 1);
+/// request.respond(5).unwrap();
+/// assert!(response.await == Ok(5));
+/// # })
+/// ```
+pub async fn select_any<Req, Resp, M>(
+    responders: &mut [Responder<Req, Resp, M>],
+) -> Result<(usize, ReceivedRequest<Req, Resp, M>), RecvError> {
+    let start = match responders.len() {
+        0 => return Err(RecvError),
+        len => RandomState::new().build_hasher().finish() as usize % len,
+    };
+    poll_fn(|cx| {
+        let len = responders.len();
+        let mut open = false;
+        for index in (start..len).chain(0..start) {
+            let responder = &mut responders[index];
+            if responder.is_terminated() {
+                continue;
+            }
+            match Pin::new(responder).poll_next(cx) {
+                Poll::Ready(Some(received)) => return Poll::Ready(Ok((index, received))),
+                Poll::Ready(None) => (),
+                Poll::Pending => open = true,
+            }
+        }
+        match open {
+            true => Poll::Pending,
+            false => Poll::Ready(Err(RecvError)),
+        }
+    })
+    .await
+}
+
+/// Take the next message off the queue, or find out why there isn't one
+fn try_pop<Req, Resp, M>(
+    incoming: &Queue<Req, Resp, M>,
+    limit: &Limit,
+) -> Result<Message<Req, Resp, M>, TryRecvError> {
+    if let Some(message) = incoming.try_recv() {
+        return Ok(message);
+    }
+    // Checked before looking again, so whatever was put on the queue before it finished is found
+    let finished = limit.is_finished();
+    match incoming.try_recv() {
+        Some(message) => Ok(message),
+        None if finished => Err(TryRecvError::Closed),
+        None => Err(TryRecvError::Empty),
+    }
+}
+
+/// Take the next message off the queue, registering `cx` to be woken while it's empty.
+/// `None` once the channel is closed and empty
+fn poll_pop<Req, Resp, M>(
+    incoming: &Queue<Req, Resp, M>,
+    limit: &Limit,
+    listener: &mut Option<EventListener>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<Message<Req, Resp, M>>> {
+    loop {
+        match try_pop(incoming, limit) {
+            Ok(message) => {
+                *listener = None;
+                return Poll::Ready(Some(message));
+            }
+            Err(TryRecvError::Closed) => {
+                *listener = None;
+                return Poll::Ready(None);
+            }
+            Err(TryRecvError::Empty) => (),
+        }
+        match listener {
+            // A message may have arrived before we started listening, so check again
+            None => *listener = Some(limit.listen_arrived()),
+            Some(waiting) => {
+                futures::ready!(Pin::new(waiting).poll(cx));
+                *listener = None;
+            }
+        }
+    }
+}
+
+/// Every request taken off the channel goes through here
+fn picked_up<Req, Resp, M>(
+    mut received: ReceivedRequest<Req, Resp, M>,
+    incoming: &Queue<Req, Resp, M>,
+) -> ReceivedRequest<Req, Resp, M> {
+    received.unresponded.pick_up(incoming.len());
+    received
+}
+
+/// Extra functionality for a [`Responder`]
+pub trait ResponderExt<Req, Resp, M = ()> {
+    /// Receive a request, recording when it was taken off the channel.
+    /// This lets [`Requester::send_detailed`](crate::Requester::send_detailed) report queue and processing times.
+    fn recv_stamped(&self) -> RecvStamped<'_, Req, Resp, M>;
+
+    /// Receive a request once fewer than `max_inflight` requests are waiting for a response in `pending`.
+    /// Requests are left on the channel until then, so backpressure reaches the [`Requester`](crate::Requester)s
+    /// according to how fast requests are actually handled, rather than just the channel's capacity.
+    /// The returned request counts towards `pending` until it is responded to, or dropped.
+    fn recv_limited<'a>(
+        &'a self,
+        max_inflight: usize,
+        pending: &'a PendingResponses,
+    ) -> impl Future<Output = Result<ReceivedRequest<Req, Resp, M>, RecvError>> + 'a;
+
+    /// Merge several [`Responder`]s into a single [`Stream`](futures::Stream) of requests, for handling centrally.
+    /// Each request keeps its own handle, so responses are routed back to the right [`Requester`](crate::Requester).
+    /// The stream ends once every merged channel is closed and empty
+    fn merge<I>(responders: I) -> SelectAll<Self>
+    where
+        I: IntoIterator<Item = Self>,
+        Self: Sized;
+
+    /// Group requests which share a key, for answering together (e.g. with one batched lookup).
+    /// A group is yielded `window` after its first request arrived, with every request for that key which arrived in the meantime.
+    /// Each request keeps its own handle, so every [`Requester`](crate::Requester) gets its own response.
+    /// Any remaining groups are yielded straight away once the channel is closed.
+    fn coalesce_by<K, F>(self, key_fn: F, window: Duration) -> Coalesce<Req, Resp, K, F, M>
+    where
+        F: FnMut(&Req) -> K,
+        K: PartialEq,
+        Self: Sized;
+
+    /// Check each request with `validate` before it reaches the handler.
+    /// Requests which fail are responded to with the returned rejection, and never yielded.
+    fn validated<F>(self, validate: F) -> Validated<Req, Resp, F, M>
+    where
+        F: Fn(&Req) -> Result<(), Resp>,
+        Self: Sized;
+
+    /// Transform each request with `f` before it reaches the handler.
+    /// Each request keeps its own handle, so the response still goes to the original [`Requester`](crate::Requester).
+    fn map_request<Req2, F>(self, f: F) -> MapRequest<Req, Resp, F, M>
+    where
+        F: FnMut(Req) -> Req2,
+        Self: Sized;
+
+    /// Drain every request currently waiting on the channel, for handing over with [`Requester::inject`](crate::Requester::inject).
+    /// The requests keep their handles, so their [`Requester`](crate::Requester)s are still answered after the handover.
+    /// Requests sent after the drain are not included.
+    fn take_pending(self) -> Vec<ReceivedRequest<Req, Resp, M>>
+    where
+        Self: Sized;
+
+    /// Limit requests to `rate` per second, using a token bucket.
+    /// Requests are left on the channel until they may be yielded, so backpressure reaches the [`Requester`](crate::Requester)s,
+    /// however many there are.
+    /// Allows a burst of one request by default - see [`Throttled::burst`].
+    ///
+    /// # Panics
+    /// If `rate` isn't positive
+    fn throttled(self, rate: f64) -> Throttled<Req, Resp, M>
+    where
+        Self: Sized;
+
+    /// Keep track of which yielded requests are still owed a response, for diagnosing handlers that leak their handles.
+    /// Unlike [`PendingResponses`], this enumerates the owed responses - see [`OwedStream::owed`].
+    fn into_owed_stream(self) -> OwedStream<Req, Resp, M>
+    where
+        Self: Sized;
+
+    /// Answer requests sent with [`Requester::send_idempotent`](crate::Requester::send_idempotent) from a cache of responses,
+    /// if their token has been seen before.
+    /// Duplicates which arrive while the first is still being handled wait for its response.
+    fn idempotent(self, config: IdempotencyConfig) -> Idempotent<Req, Resp, M>
+    where
+        Resp: Clone + Send + 'static,
+        M: Send + 'static,
+        Self: Sized;
+}
+
+impl<Req, Resp, M> ResponderExt<Req, Resp, M> for Responder<Req, Resp, M> {
+    fn recv_stamped(&self) -> RecvStamped<'_, Req, Resp, M> {
+        RecvStamped { recv: self.recv() }
+    }
+
+    async fn recv_limited<'a>(
+        &'a self,
+        max_inflight: usize,
+        pending: &'a PendingResponses,
+    ) -> Result<ReceivedRequest<Req, Resp, M>, RecvError> {
+        let guard = pending.reserve(max_inflight).await;
+        let mut received = self.recv().await?;
+        received.unresponded.trackers.push(Box::new(guard));
+        Ok(received)
+    }
+
+    fn merge<I>(responders: I) -> SelectAll<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        select_all(responders)
+    }
+
+    fn coalesce_by<K, F>(self, key_fn: F, window: Duration) -> Coalesce<Req, Resp, K, F, M>
+    where
+        F: FnMut(&Req) -> K,
+        K: PartialEq,
+    {
+        Coalesce {
+            responder: Some(self),
+            key_fn,
+            window,
+            groups: Vec::new(),
+            timer: None,
+        }
+    }
+
+    fn validated<F>(self, validate: F) -> Validated<Req, Resp, F, M>
+    where
+        F: Fn(&Req) -> Result<(), Resp>,
+    {
+        Validated {
+            responder: self,
+            validate,
+        }
+    }
+
+    fn map_request<Req2, F>(self, f: F) -> MapRequest<Req, Resp, F, M>
+    where
+        F: FnMut(Req) -> Req2,
+    {
+        MapRequest { responder: self, f }
+    }
+
+    fn take_pending(self) -> Vec<ReceivedRequest<Req, Resp, M>> {
+        std::iter::from_fn(|| self.try_recv().ok()).collect()
+    }
+
+    fn throttled(self, rate: f64) -> Throttled<Req, Resp, M> {
+        assert!(rate > 0.0, "rate must be positive");
+        Throttled {
+            responder: self,
+            rate,
+            burst: 1.0,
+            tokens: 1.0,
+            refilled: Instant::now(),
+            timer: None,
+        }
+    }
+
+    fn into_owed_stream(self) -> OwedStream<Req, Resp, M> {
+        OwedStream::new(self)
+    }
+
+    fn idempotent(self, config: IdempotencyConfig) -> Idempotent<Req, Resp, M>
+    where
+        Resp: Clone + Send + 'static,
+        M: Send + 'static,
+    {
+        Idempotent::new(self, config)
+    }
+}
+
+/// Future returned by [`ResponderExt::recv_stamped`]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvStamped<'a, Req, Resp, M = ()> {
+    recv: Recv<'a, Req, Resp, M>,
+}
+
+impl<'a, Req, Resp, M> Future for RecvStamped<'a, Req, Resp, M> {
+    type Output = Result<ReceivedRequest<Req, Resp, M>, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.recv).poll(cx).map_ok(|mut received| {
+            received.unresponded.received_at = Some(Instant::now());
+            received
+        })
+    }
+}
+
+/// Stream returned by [`ResponderExt::coalesce_by`]
+#[must_use = "streams do nothing unless polled"]
+pub struct Coalesce<Req, Resp, K, F, M = ()> {
+    /// `None` once the channel is exhausted
+    responder: Option<Responder<Req, Resp, M>>,
+    key_fn: F,
+    window: Duration,
+    /// Every group has the same window, so these are in deadline order
+    groups: Vec<Group<Req, Resp, K, M>>,
+    /// Fires at the deadline of the first group
+    timer: Option<(Instant, Delay)>,
+}
+
+struct Group<Req, Resp, K, M> {
+    key: K,
+    deadline: Instant,
+    requests: Vec<ReceivedRequest<Req, Resp, M>>,
+}
+
+// `key_fn` is only called through `&mut`, and keys live in the heap-allocated `groups`
+impl<Req, Resp, K, F, M> Unpin for Coalesce<Req, Resp, K, F, M> {}
+
+impl<Req, Resp, K, F, M> Stream for Coalesce<Req, Resp, K, F, M>
+where
+    F: FnMut(&Req) -> K,
+    K: PartialEq,
+{
+    type Item = Vec<ReceivedRequest<Req, Resp, M>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        while let Some(responder) = &mut this.responder {
+            match Pin::new(responder).poll_next(cx) {
+                Poll::Ready(Some(request)) => {
+                    let key = (this.key_fn)(&request);
+                    match this.groups.iter_mut().find(|group| group.key == key) {
+                        Some(group) => group.requests.push(request),
+                        None => this.groups.push(Group {
+                            key,
+                            deadline: Instant::now() + this.window,
+                            requests: vec![request],
+                        }),
+                    }
+                }
+                Poll::Ready(None) => this.responder = None,
+                Poll::Pending => break,
+            }
+        }
+
+        let deadline = match this.groups.first() {
+            Some(group) => group.deadline,
+            None if this.responder.is_none() => return Poll::Ready(None),
+            None => return Poll::Pending,
+        };
+        let now = Instant::now();
+        if this.responder.is_none() || deadline <= now {
+            return Poll::Ready(Some(this.groups.remove(0).requests));
+        }
+        match &mut this.timer {
+            Some((at, _)) if *at == deadline => {}
+            timer => *timer = Some((deadline, Delay::new(deadline - now))),
+        }
+        let (_, delay) = this.timer.as_mut().expect("timer was just set");
+        match Pin::new(delay).poll(cx) {
+            Poll::Ready(()) => {
+                this.timer = None;
+                Poll::Ready(Some(this.groups.remove(0).requests))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A [`Responder`] which only yields valid requests, returned by [`ResponderExt::validated`].
+/// Receive with [`Validated::recv`], or use it as a [`Stream`]
+pub struct Validated<Req, Resp, F, M = ()> {
+    responder: Responder<Req, Resp, M>,
+    validate: F,
+}
+
+impl<Req, Resp, F, M> Validated<Req, Resp, F, M>
+where
+    F: Fn(&Req) -> Result<(), Resp>,
+{
+    /// Receive the next valid request, rejecting any invalid ones in the meantime.
+    /// Fails if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    pub async fn recv(&self) -> Result<ReceivedRequest<Req, Resp, M>, RecvError> {
+        loop {
+            if let Some(request) = self.check(self.responder.recv().await?) {
+                return Ok(request);
+            }
+        }
+    }
+
+    /// Recover the underlying [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp, M> {
+        self.responder
+    }
+
+    fn check(
+        &self,
+        received: ReceivedRequest<Req, Resp, M>,
+    ) -> Option<ReceivedRequest<Req, Resp, M>> {
+        match (self.validate)(&received) {
+            Ok(()) => Some(received),
+            Err(rejection) => {
+                // The requester may have gone away, which is fine
+                let _ = received.respond(rejection);
+                None
+            }
+        }
+    }
+}
+
+// `validate` is only called by reference, so it doesn't need to stay put
+impl<Req, Resp, F, M> Unpin for Validated<Req, Resp, F, M> {}
+
+impl<Req, Resp, F, M> Stream for Validated<Req, Resp, F, M>
+where
+    F: Fn(&Req) -> Result<(), Resp>,
+{
+    type Item = ReceivedRequest<Req, Resp, M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match futures::ready!(Pin::new(&mut self.responder).poll_next(cx)) {
+                Some(received) => {
+                    if let Some(request) = self.check(received) {
+                        return Poll::Ready(Some(request));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Stream returned by [`ResponderExt::map_request`]
+#[must_use = "streams do nothing unless polled"]
+pub struct MapRequest<Req, Resp, F, M = ()> {
+    responder: Responder<Req, Resp, M>,
+    f: F,
+}
+
+impl<Req, Resp, F, M> MapRequest<Req, Resp, F, M> {
+    /// Recover the underlying [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp, M> {
+        self.responder
+    }
+}
+
+// `f` is an ordinary closure, called through `&mut` on each request
+impl<Req, Resp, F, M> Unpin for MapRequest<Req, Resp, F, M> {}
+
+impl<Req, Resp, Req2, F, M> Stream for MapRequest<Req, Resp, F, M>
+where
+    F: FnMut(Req) -> Req2,
+{
+    type Item = ReceivedRequest<Req2, Resp, M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let received = futures::ready!(Pin::new(&mut self.responder).poll_next(cx));
+        Poll::Ready(received.map(
+            |ReceivedRequest {
+                 request,
+                 unresponded,
+             }| {
+                ReceivedRequest {
+                    request: (self.f)(request),
+                    unresponded,
+                }
+            },
+        ))
+    }
+}
+
+impl<Req, Resp, Req2, F, M> FusedStream for MapRequest<Req, Resp, F, M>
+where
+    F: FnMut(Req) -> Req2,
+{
+    fn is_terminated(&self) -> bool {
+        self.responder.is_terminated()
+    }
+}
+
+/// Stream returned by [`ResponderExt::throttled`]
+#[must_use = "streams do nothing unless polled"]
+pub struct Throttled<Req, Resp, M = ()> {
+    responder: Responder<Req, Resp, M>,
+    /// Tokens per second
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled: Instant,
+    /// Fires once there's a token
+    timer: Option<Delay>,
+}
+
+impl<Req, Resp, M> Throttled<Req, Resp, M> {
+    /// Allow up to `burst` requests to be yielded at once, after a quiet period.
+    /// The bucket starts full.
+    ///
+    /// # Panics
+    /// If `burst` is zero
+    pub fn burst(mut self, burst: usize) -> Self {
+        assert!(burst > 0, "burst must be positive");
+        self.burst = burst as f64;
+        self.tokens = self.burst;
+        self
+    }
+
+    /// Recover the underlying [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp, M> {
+        self.responder
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled = now;
+    }
+}
+
+impl<Req, Resp, M> Stream for Throttled<Req, Resp, M> {
+    type Item = ReceivedRequest<Req, Resp, M>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            this.refill();
+            if this.tokens >= 1.0 {
+                break;
+            }
+            let wait = Duration::from_secs_f64((1.0 - this.tokens) / this.rate);
+            let timer = this.timer.get_or_insert_with(|| Delay::new(wait));
+            futures::ready!(Pin::new(timer).poll(cx));
+            this.timer = None;
+        }
+        let received = futures::ready!(Pin::new(&mut this.responder).poll_next(cx));
+        if received.is_some() {
+            this.tokens -= 1.0;
+        }
+        Poll::Ready(received)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{lock, Response};
+use futures::{
+    channel::mpsc,
+    stream::{FusedStream, FuturesOrdered},
+    Stream, StreamExt,
+};
+use std::{
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
+
+/// A [`Stream`] of the responses to requests sent with [`Requester::send_and_forget`](crate::Requester::send_and_forget)
+/// (or through a [`RequestSink`](crate::RequestSink)), created by [`Requester::responses`](crate::Requester::responses).
+///
+/// Responses are yielded in the order their requests were put on the channel, so a slow response holds up the ones after it.
+/// Requests which weren't responded to are skipped.
+/// The stream ends once every [`Requester`](crate::Requester) is dropped (or another [`Responses`] takes over), and every response has arrived
+#[must_use = "streams do nothing unless polled"]
+pub struct Responses<Resp> {
+    incoming: mpsc::UnboundedReceiver<Response<Resp>>,
+    pending: FuturesOrdered<Response<Resp>>,
+}
+
+impl<Resp> Responses<Resp> {
+    pub(crate) fn new(incoming: mpsc::UnboundedReceiver<Response<Resp>>) -> Self {
+        Self {
+            incoming,
+            pending: FuturesOrdered::new(),
+        }
+    }
+}
+
+impl<Resp> Stream for Responses<Resp> {
+    type Item = Resp;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Resp>> {
+        while let Poll::Ready(Some(response)) = self.incoming.poll_next_unpin(cx) {
+            self.pending.push_back(response)
+        }
+        loop {
+            match self.pending.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(response))) => return Poll::Ready(Some(response)),
+                // Ignored, so skip it
+                Poll::Ready(Some(Err(_))) => continue,
+                Poll::Ready(None) if self.incoming.is_terminated() => return Poll::Ready(None),
+                // Waiting on more requests
+                Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<Resp> FusedStream for Responses<Resp> {
+    fn is_terminated(&self) -> bool {
+        self.incoming.is_terminated() && self.pending.is_empty()
+    }
+}
+
+/// Where [`Requester::send_and_forget`](crate::Requester::send_and_forget) puts its responses, shared by every [`Requester`](crate::Requester)
+pub(crate) struct Collector<Resp> {
+    sender: Mutex<Option<mpsc::UnboundedSender<Response<Resp>>>>,
+}
+
+impl<Resp> Collector<Resp> {
+    pub(crate) fn new() -> Self {
+        Self {
+            sender: Mutex::new(None),
+        }
+    }
+
+    /// Start collecting into a new [`Responses`], ending the previous one
+    pub(crate) fn responses(&self) -> Responses<Resp> {
+        let (sender, receiver) = mpsc::unbounded();
+        *lock(&self.sender) = Some(sender);
+        Responses::new(receiver)
+    }
+
+    /// Hand `response` to the [`Responses`] stream, if there is one.
+    /// Otherwise, it is dropped
+    pub(crate) fn collect(&self, response: Response<Resp>) {
+        let mut sender = lock(&self.sender);
+        if let Some(open) = &*sender {
+            if open.unbounded_send(response).is_err() {
+                // The stream was dropped, so stop collecting
+                *sender = None;
+            }
+        }
+    }
+}
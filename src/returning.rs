@@ -0,0 +1,166 @@
+//! Channels which hand ignored requests back to the [`ReturningRequester`], for retrying.
+//!
+//! Each request is wrapped in a [`Returnable`], which sends the request back if it's dropped without being taken.
+//! Because [`ReceivedRequest`](crate::ReceivedRequest) drops its request before its [`UnRespondedRequest`](crate::UnRespondedRequest),
+//! a request that's dropped unanswered comes back with [`ReturningSendError::Ignored`].
+//!
+//! This costs an extra allocation per request, for the return path.
+//!
+//! ```
+//! use bidirectional_channel::returning::{bounded, ReturningSendError};
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! let (response, _) = join!(requester.send("hello"), async {
+//!     drop(responder.recv().await.unwrap())
+//! });
+//! assert!(matches!(response, Err(ReturningSendError::Ignored("hello"))))
+//! # })
+//! ```
+
+use crate::{Requester, Responder, SendRequestError};
+use derive_more::{AsMut, AsRef};
+use futures::channel::oneshot;
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+use thiserror::Error;
+
+/// Error returned when sending a request with a [`ReturningRequester`]
+#[derive(Error)]
+pub enum ReturningSendError<Req> {
+    /// The [`Responder`] for this channel was dropped.
+    /// Returns ownership of the `Req` that failed to send
+    #[error("The Responder was dropped before the message was sent")]
+    Closed(Req),
+    /// The request was dropped, not responded to.
+    /// Returns ownership of the `Req`
+    #[error("The UnRespondedRequest was dropped, not responded to")]
+    Ignored(Req),
+    /// The request was not responded to, and the [`Responder`] took the `Req` with [`Returnable::into_inner`]
+    #[error("The UnRespondedRequest was dropped, and the request was kept")]
+    Retained,
+}
+impl<Req> Debug for ReturningSendError<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "Closed(..)"),
+            Self::Ignored(_) => write!(f, "Ignored(..)"),
+            Self::Retained => write!(f, "Retained"),
+        }
+    }
+}
+
+/// A request which is sent back to its [`ReturningRequester`] if it's dropped.
+/// This implements [`Deref`] and [`DerefMut`] for the request itself.
+#[derive(AsRef, AsMut)]
+pub struct Returnable<Req> {
+    /// `None` once taken
+    #[as_ref]
+    #[as_mut]
+    request: Option<Req>,
+    return_path: Option<oneshot::Sender<Req>>,
+}
+
+impl<Req> Returnable<Req> {
+    /// Take ownership of the request, so it won't be returned
+    pub fn into_inner(mut self) -> Req {
+        self.request.take().expect("only taken once")
+    }
+}
+
+impl<Req> Deref for Returnable<Req> {
+    type Target = Req;
+    fn deref(&self) -> &Req {
+        self.request.as_ref().expect("only taken on drop")
+    }
+}
+
+impl<Req> DerefMut for Returnable<Req> {
+    fn deref_mut(&mut self) -> &mut Req {
+        self.request.as_mut().expect("only taken on drop")
+    }
+}
+
+impl<Req: Debug> Debug for Returnable<Req> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Returnable").field(&self.request).finish()
+    }
+}
+
+impl<Req> Drop for Returnable<Req> {
+    fn drop(&mut self) {
+        if let (Some(request), Some(return_path)) = (self.request.take(), self.return_path.take()) {
+            let _ = return_path.send(request);
+        }
+    }
+}
+
+/// A [`Requester`] which gets ignored requests back
+pub struct ReturningRequester<Req, Resp> {
+    inner: Requester<Returnable<Req>, Resp>,
+}
+
+impl<Req, Resp> Clone for ReturningRequester<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Req, Resp> ReturningRequester<Req, Resp> {
+    /// Make a request.
+    /// `await` the result to receive the response.
+    /// Fails if the [`Responder`] was dropped, or the request wasn't responded to, and returns your request back where possible
+    pub async fn send(&self, request: Req) -> Result<Resp, ReturningSendError<Req>> {
+        let (return_path, mut returned) = oneshot::channel();
+        let request = Returnable {
+            request: Some(request),
+            return_path: Some(return_path),
+        };
+        match self.inner.send(request).await {
+            Ok(response) => Ok(response),
+            Err(SendRequestError::Closed(request)) => {
+                Err(ReturningSendError::Closed(request.into_inner()))
+            }
+            // A shed request is dropped unanswered too
+            Err(SendRequestError::Ignored) | Err(SendRequestError::Shed) => {
+                match returned.try_recv() {
+                    Ok(Some(request)) => Err(ReturningSendError::Ignored(request)),
+                    _ => Err(ReturningSendError::Retained),
+                }
+            }
+            Err(SendRequestError::TimedOut(_)) | Err(SendRequestError::ResponseTimedOut) => {
+                unreachable!("no timeout was set")
+            }
+            Err(SendRequestError::Cancelled) => unreachable!("the request can't be cancelled"),
+            Err(SendRequestError::Full(_)) => unreachable!("the channel waits while it's full"),
+        }
+    }
+
+    /// Recover the underlying [`Requester`]
+    pub fn into_inner(self) -> Requester<Returnable<Req>, Resp> {
+        self.inner
+    }
+}
+
+/// A [`Responder`] for a [`ReturningRequester`]
+pub type ReturningResponder<Req, Resp> = Responder<Returnable<Req>, Resp>;
+
+/// Create a bounded [`ReturningRequester`]-[`Responder`] pair.
+/// See [`crate::bounded`]
+pub fn bounded<Req, Resp>(
+    capacity: usize,
+) -> (ReturningRequester<Req, Resp>, ReturningResponder<Req, Resp>) {
+    let (inner, responder) = crate::bounded(capacity);
+    (ReturningRequester { inner }, responder)
+}
+
+/// Create an unbounded [`ReturningRequester`]-[`Responder`] pair.
+/// See [`crate::unbounded`]
+pub fn unbounded<Req, Resp>() -> (ReturningRequester<Req, Resp>, ReturningResponder<Req, Resp>) {
+    let (inner, responder) = crate::unbounded();
+    (ReturningRequester { inner }, responder)
+}
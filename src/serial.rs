@@ -0,0 +1,77 @@
+use crate::{ReceivedRequest, RecvError, Responder};
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use std::marker::PhantomData;
+
+/// A [`Responder`] for strictly sequential handlers.
+/// Each [`SerialRequest`] borrows the responder, so the borrow checker won't let you receive again until you've responded.
+///
+/// ```compile_fail
+/// # async_std::task::block_on(async {
+/// use bidirectional_channel::{bounded, SerialResponder};
+/// let (requester, responder) = bounded::<&str, usize>(2);
+/// let mut responder = SerialResponder::from(responder);
+/// let first = responder.recv().await.unwrap();
+/// let second = responder.recv().await.unwrap(); // `first` must be responded to first
+/// first.respond(1).unwrap();
+/// # })
+/// ```
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// use bidirectional_channel::{bounded, SerialResponder};
+/// let (requester, responder) = bounded::<&str, usize>(2);
+/// requester.send_and_forget("hello").await.unwrap();
+/// requester.send_and_forget("world").await.unwrap();
+/// let mut responder = SerialResponder::from(responder);
+/// let first = responder.recv().await.unwrap();
+/// let _ = first.respond(1);
+/// let second = responder.recv().await.unwrap();
+/// let _ = second.respond(2);
+/// # })
+/// ```
+pub struct SerialResponder<Req, Resp> {
+    inner: Responder<Req, Resp>,
+}
+
+impl<Req, Resp> SerialResponder<Req, Resp> {
+    /// Receive a request, which must be responded to (or dropped) before receiving again.
+    /// Fails if every [`Requester`](crate::Requester) was dropped, and the channel is empty
+    pub async fn recv(&mut self) -> Result<SerialRequest<'_, Req, Resp>, RecvError> {
+        Ok(SerialRequest {
+            received: self.inner.recv().await?,
+            responder: PhantomData,
+        })
+    }
+
+    /// Recover the underlying [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp> {
+        self.inner
+    }
+}
+
+impl<Req, Resp> From<Responder<Req, Resp>> for SerialResponder<Req, Resp> {
+    fn from(inner: Responder<Req, Resp>) -> Self {
+        Self { inner }
+    }
+}
+
+/// A [`ReceivedRequest`] which borrows its [`SerialResponder`] until it's responded to.
+/// This implements [`AsRef`], [`AsMut`], [`Deref`](std::ops::Deref) and [`DerefMut`](std::ops::DerefMut) for the request itself
+#[must_use = "You must respond to the request"]
+#[derive(AsRef, AsMut, Deref, DerefMut)]
+pub struct SerialRequest<'a, Req, Resp> {
+    #[as_ref(forward)]
+    #[as_mut(forward)]
+    #[deref(forward)]
+    #[deref_mut(forward)]
+    received: ReceivedRequest<Req, Resp>,
+    responder: PhantomData<&'a mut ()>,
+}
+
+impl<'a, Req, Resp> SerialRequest<'a, Req, Resp> {
+    /// Respond to the [`Requester`](crate::Requester)'s request, and take ownership of it, releasing the [`SerialResponder`].
+    /// Fails if the associated [`Requester`](crate::Requester) was dropped, and returns your response back
+    pub fn respond(self, response: Resp) -> Result<Req, (Req, Resp)> {
+        self.received.respond(response)
+    }
+}
@@ -0,0 +1,97 @@
+//! Drive a [`Responder`] with a [`tower::Service`], so existing handlers and middleware stacks can sit behind a channel.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, service::serve};
+//! use tower::service_fn;
+//! # use std::convert::Infallible;
+//! # use futures::join;
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! let service = service_fn(|request: &'static str| async move { Ok::<_, Infallible>(request.len()) });
+//! let client = async move { requester.send("hello").await.unwrap() };
+//! let (response, ()) = join!(client, serve(responder, service));
+//! assert!(response == 5);
+//! # })
+//! ```
+
+use crate::{Responder, UnRespondedRequest};
+use futures::{
+    future::poll_fn,
+    stream::{FuturesUnordered, StreamExt},
+    Future,
+};
+use std::task::Poll;
+use tower::Service;
+
+/// Respond to each request on the channel with the output of `service`, until every [`Requester`](crate::Requester) is dropped.
+///
+/// Requests are only taken off the channel while [`Service::poll_ready`] is ready, so a busy service applies backpressure to the channel.
+/// Calls run concurrently, and their requests are responded to as they complete.
+///
+/// If a call fails, its [`UnRespondedRequest`] is dropped, so the [`Requester`](crate::Requester) sees [`SendRequestError::Ignored`](crate::SendRequestError::Ignored).
+/// See [`serve_fallible`] to send the error back instead.
+/// If the service itself fails to become ready, this returns, dropping any calls in flight.
+pub async fn serve<Req, Resp, S>(responder: Responder<Req, Resp>, service: S)
+where
+    S: Service<Req, Response = Resp>,
+{
+    drive(responder, service, Result::ok).await
+}
+
+/// Like [`serve`], but the [`Requester`](crate::Requester) receives the service's errors too.
+/// Pair this with [`Requester::send_fallible`](crate::Requester::send_fallible)
+pub async fn serve_fallible<Req, S>(
+    responder: Responder<Req, Result<S::Response, S::Error>>,
+    service: S,
+) where
+    S: Service<Req>,
+{
+    drive(responder, service, Some).await
+}
+
+/// What to send back for a call's output, if anything
+type ToResponse<T, E, Resp> = fn(Result<T, E>) -> Option<Resp>;
+
+async fn drive<Req, Resp, S>(
+    mut responder: Responder<Req, Resp>,
+    mut service: S,
+    to_response: ToResponse<S::Response, S::Error, Resp>,
+) where
+    S: Service<Req>,
+{
+    let mut in_flight = FuturesUnordered::new();
+    poll_fn(|cx| loop {
+        // Finish whatever we can, which also wakes us when the rest finish
+        while let Poll::Ready(Some(())) = in_flight.poll_next_unpin(cx) {}
+        match service.poll_ready(cx) {
+            Poll::Ready(Ok(())) => (),
+            Poll::Ready(Err(_)) => return Poll::Ready(()),
+            Poll::Pending => return Poll::Pending,
+        }
+        match responder.poll_next_unpin(cx) {
+            Poll::Ready(Some(received)) => {
+                let (request, unresponded) = received.into();
+                let call = service.call(request);
+                in_flight.push(respond(call, unresponded, to_response));
+            }
+            Poll::Ready(None) if in_flight.is_empty() => return Poll::Ready(()),
+            // Still waiting on calls in flight
+            Poll::Ready(None) => return Poll::Pending,
+            Poll::Pending => return Poll::Pending,
+        }
+    })
+    .await
+}
+
+async fn respond<Resp, F, T, E>(
+    call: F,
+    unresponded: UnRespondedRequest<Resp>,
+    to_response: ToResponse<T, E, Resp>,
+) where
+    F: Future<Output = Result<T, E>>,
+{
+    // Dropping the handle tells the requester it was ignored
+    if let Some(response) = to_response(call.await) {
+        let _ = unresponded.respond(response);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{EnqueueError, Requester};
+use futures::{future::BoxFuture, FutureExt, Sink};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A [`Sink`] of requests, created by [`Requester::into_sink`].
+/// Requests are put on the channel without waiting for a response, like [`Requester::send_and_forget`],
+/// so the [`Responder`](crate::Responder) can't respond to them - it's best suited to `Resp = ()`.
+///
+/// ```
+/// use bidirectional_channel::bounded;
+/// use futures::{stream, StreamExt};
+/// # async_std::task::block_on(async {
+/// let (requester, responder) = bounded::<usize, ()>(1);
+/// let forward = stream::iter(0..3).map(Ok).forward(requester.into_sink());
+/// let (forwarded, received) = futures::join!(forward, responder.map(|request| *request).collect::<Vec<_>>());
+/// assert!(forwarded.is_ok() && received == [0, 1, 2]);
+/// # })
+/// ```
+#[must_use = "sinks do nothing unless polled"]
+pub struct RequestSink<Req, Resp, M = ()> {
+    requester: Requester<Req, Resp, M>,
+    /// The request being put on the channel
+    in_flight: Option<BoxFuture<'static, Result<(), EnqueueError<Req>>>>,
+}
+
+impl<Req, Resp, M> RequestSink<Req, Resp, M> {
+    pub(crate) fn new(requester: Requester<Req, Resp, M>) -> Self {
+        Self {
+            requester,
+            in_flight: None,
+        }
+    }
+
+    /// Recover the underlying [`Requester`].
+    /// Any request which was still being put on the channel is dropped
+    pub fn into_inner(self) -> Requester<Req, Resp, M> {
+        self.requester
+    }
+
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), EnqueueError<Req>>> {
+        if let Some(in_flight) = &mut self.in_flight {
+            let result = futures::ready!(in_flight.poll_unpin(cx));
+            self.in_flight = None;
+            result?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<Req, Resp, M> Sink<Req> for RequestSink<Req, Resp, M>
+where
+    Req: Send + 'static,
+    Resp: Send + 'static,
+    M: Default + Send + 'static,
+{
+    type Error = EnqueueError<Req>;
+
+    /// Ready once the previous request is on the channel
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_in_flight(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, request: Req) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        let requester = this.requester.clone();
+        this.in_flight = Some(async move { requester.send_and_forget(request).await }.boxed());
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_in_flight(cx)
+    }
+
+    /// Flushes, but leaves the channel open for other [`Requester`]s
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.get_mut().poll_in_flight(cx)
+    }
+}
@@ -0,0 +1,79 @@
+//! Runtime-agnostic task spawning, used by the helpers which drive a channel in the background.
+//!
+//! Implementations are provided for async-std behind the `async-std` feature (on by default), and for tokio behind the `tokio` feature.
+//! If you're on a different runtime, implement [`Spawn`] for your executor.
+//!
+//! ```
+//! use bidirectional_channel::{bounded, spawn::{AsyncStd, Spawn}};
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! AsyncStd.spawn(async move {
+//!     while let Ok(request) = responder.recv().await {
+//!         let len = request.len();
+//!         let _ = request.respond(len);
+//!     }
+//! });
+//! assert!(requester.send("hello").await.unwrap() == 5)
+//! # })
+//! ```
+
+use std::future::Future;
+
+/// Something which can run a future to completion in the background
+pub trait Spawn {
+    /// Run the future in the background.
+    /// The future should be driven to completion even if nothing waits for it to finish.
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static;
+}
+
+impl<S: Spawn + ?Sized> Spawn for &S {
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        (**self).spawn(future)
+    }
+}
+
+/// Spawns onto the global async-std executor
+#[cfg(feature = "async-std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncStd;
+
+#[cfg(feature = "async-std")]
+impl Spawn for AsyncStd {
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        async_std::task::spawn(future);
+    }
+}
+
+/// Spawns onto the current tokio runtime.
+/// Spawning panics if called from outside a tokio runtime - use a [`tokio::runtime::Handle`] in that case.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tokio;
+
+#[cfg(feature = "tokio")]
+impl Spawn for Tokio {
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Spawn for tokio::runtime::Handle {
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::runtime::Handle::spawn(self, future);
+    }
+}
@@ -0,0 +1,230 @@
+//! Requests which are answered with a stream of responses, rather than just one.
+//!
+//! Each request has its own bounded response buffer.
+//! Once it's full, [`StreamingRequest::respond_backpressured`] waits for the [`StreamingRequester`] to catch up,
+//! so the [`StreamingResponder`] only produces responses as fast as they are consumed.
+//! The request queue is sized separately, with [`streaming`], [`bounded`] or [`unbounded`],
+//! so a slow consumer of one stream holds up its responses, but not new requests.
+//! [`bounded_streaming`](crate::bounded_streaming) and [`unbounded_streaming`](crate::unbounded_streaming) at the crate root are the same constructors.
+//!
+//! Whether requests are answered with a stream is fixed when the channel is created, rather than chosen per request,
+//! because a [`Requester`](crate::Requester) is already awaiting a single response by the time its request is received.
+//! So there's no way to turn an [`UnRespondedRequest`](crate::UnRespondedRequest) into a stream:
+//! a [`StreamingRequest`] carries a [`ResponseSender`] instead, which [`StreamingRequest::split`] hands out.
+//!
+//! ```
+//! use bidirectional_channel::streaming::bounded;
+//! use futures::{join, StreamExt};
+//! # async_std::task::block_on(async {
+//! let (requester, responder) = bounded::<usize, usize>(1, 1);
+//! let requester = async {
+//!     let responses = requester.send(3).await.unwrap();
+//!     responses.collect::<Vec<_>>().await
+//! };
+//! let responder = async {
+//!     let request = responder.recv().await.unwrap();
+//!     for i in 0..*request {
+//!         request.respond_backpressured(i).await.unwrap();
+//!     }
+//! };
+//! let (responses, _) = join!(requester, responder);
+//! assert!(responses == [0, 1, 2])
+//! # })
+//! ```
+
+use crate::{EnqueueError, RecvError};
+use async_channel as channel;
+use derive_more::{AsMut, AsRef, Deref, DerefMut};
+use futures::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The responses to a request made with [`StreamingRequester::send`].
+/// Ends once the [`StreamingRequest`] is dropped
+pub struct ResponseStream<Item> {
+    responses: channel::Receiver<Item>,
+}
+
+impl<Item> Stream for ResponseStream<Item> {
+    type Item = Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Item>> {
+        Pin::new(&mut self.responses).poll_next(cx)
+    }
+}
+
+/// Represents a request which expects a stream of responses.
+/// Dereferences to the request itself, like [`ReceivedRequest`](crate::ReceivedRequest).
+/// Drop it to end the stream.
+#[must_use = "You must respond to the request"]
+#[derive(AsRef, AsMut, Deref, DerefMut)]
+pub struct StreamingRequest<Req, Item> {
+    /// The request itself
+    #[as_ref]
+    #[as_mut]
+    #[deref]
+    #[deref_mut]
+    pub request: Req,
+    responses: ResponseSender<Item>,
+}
+
+impl<Req, Item> StreamingRequest<Req, Item> {
+    /// Add a response to the stream, waiting while the [`StreamingRequester`]'s buffer is full.
+    /// Fails as soon as the [`ResponseStream`] is dropped, and returns your response back
+    pub async fn respond_backpressured(&self, response: Item) -> Result<(), Item> {
+        self.responses.send(response).await
+    }
+
+    /// Whether the [`ResponseStream`] was dropped, so further responses are pointless
+    pub fn is_abandoned(&self) -> bool {
+        self.responses.is_abandoned()
+    }
+
+    /// Take ownership of the request, and split off its [`ResponseSender`], e.g. to produce responses from another task
+    pub fn split(self) -> (Req, ResponseSender<Item>) {
+        (self.request, self.responses)
+    }
+}
+
+/// Adds responses to the stream of a [`StreamingRequest`], created by [`StreamingRequest::split`].
+/// The stream ends once this, and every clone of it, is dropped
+pub struct ResponseSender<Item> {
+    responses: channel::Sender<Item>,
+}
+
+impl<Item> Clone for ResponseSender<Item> {
+    fn clone(&self) -> Self {
+        Self {
+            responses: self.responses.clone(),
+        }
+    }
+}
+
+impl<Item> ResponseSender<Item> {
+    /// Add a response to the stream.
+    /// See [`StreamingRequest::respond_backpressured`]
+    pub async fn send(&self, response: Item) -> Result<(), Item> {
+        self.responses
+            .send(response)
+            .await
+            .map_err(|e| e.into_inner())
+    }
+
+    /// Whether the [`ResponseStream`] was dropped, so further responses are pointless
+    pub fn is_abandoned(&self) -> bool {
+        self.responses.is_closed()
+    }
+}
+
+/// Sends requests which are answered with a [`ResponseStream`]
+pub struct StreamingRequester<Req, Item> {
+    outgoing: channel::Sender<StreamingRequest<Req, Item>>,
+    response_capacity: usize,
+}
+
+impl<Req, Item> Clone for StreamingRequester<Req, Item> {
+    fn clone(&self) -> Self {
+        Self {
+            outgoing: self.outgoing.clone(),
+            response_capacity: self.response_capacity,
+        }
+    }
+}
+
+impl<Req, Item> StreamingRequester<Req, Item> {
+    /// Make a request, returning the stream of responses once it's on the channel.
+    /// Fails if the [`StreamingResponder`] was dropped, and returns your request back
+    pub async fn send(&self, request: Req) -> Result<ResponseStream<Item>, EnqueueError<Req>> {
+        let (responses, response_stream) = channel::bounded(self.response_capacity);
+        self.outgoing
+            .send(StreamingRequest {
+                request,
+                responses: ResponseSender { responses },
+            })
+            .await
+            .map_err(|e| EnqueueError::Closed(e.into_inner().request))?;
+        Ok(ResponseStream {
+            responses: response_stream,
+        })
+    }
+}
+
+/// Receives requests from a [`StreamingRequester`].
+/// This is also a [`Stream`] of requests
+pub struct StreamingResponder<Req, Item> {
+    incoming: channel::Receiver<StreamingRequest<Req, Item>>,
+}
+
+impl<Req, Item> Clone for StreamingResponder<Req, Item> {
+    fn clone(&self) -> Self {
+        Self {
+            incoming: self.incoming.clone(),
+        }
+    }
+}
+
+impl<Req, Item> StreamingResponder<Req, Item> {
+    /// Receive a request.
+    /// Fails if every [`StreamingRequester`] was dropped, and the channel is empty
+    pub async fn recv(&self) -> Result<StreamingRequest<Req, Item>, RecvError> {
+        self.incoming.recv().await.map_err(|_| RecvError)
+    }
+}
+
+impl<Req, Item> Stream for StreamingResponder<Req, Item> {
+    type Item = StreamingRequest<Req, Item>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.incoming).poll_next(cx)
+    }
+}
+
+/// Create a [`StreamingRequester`]-[`StreamingResponder`] pair, sizing the two buffers independently.
+/// Up to `request_capacity` requests wait on the channel, or any number if it's `None`,
+/// and each request buffers up to `reply_capacity` responses.
+/// [`bounded`] and [`unbounded`] are shorthands for this.
+///
+/// # Panics
+/// If either capacity is zero
+pub fn streaming<Req, Item>(
+    request_capacity: Option<usize>,
+    reply_capacity: usize,
+) -> (StreamingRequester<Req, Item>, StreamingResponder<Req, Item>) {
+    assert!(reply_capacity > 0, "reply_capacity must be positive");
+    let (outgoing, receiver) = match request_capacity {
+        Some(capacity) => channel::bounded(capacity),
+        None => channel::unbounded(),
+    };
+    (
+        StreamingRequester {
+            outgoing,
+            response_capacity: reply_capacity,
+        },
+        StreamingResponder { incoming: receiver },
+    )
+}
+
+/// Create a bounded [`StreamingRequester`]-[`StreamingResponder`] pair.
+/// Up to `capacity` requests wait on the channel, and each request buffers up to `response_capacity` responses.
+///
+/// # Panics
+/// If either capacity is zero
+pub fn bounded<Req, Item>(
+    capacity: usize,
+    response_capacity: usize,
+) -> (StreamingRequester<Req, Item>, StreamingResponder<Req, Item>) {
+    streaming(Some(capacity), response_capacity)
+}
+
+/// Create an unbounded [`StreamingRequester`]-[`StreamingResponder`] pair.
+/// Any number of requests wait on the channel, and each request buffers up to `response_capacity` responses.
+///
+/// # Panics
+/// If `response_capacity` is zero
+pub fn unbounded<Req, Item>(
+    response_capacity: usize,
+) -> (StreamingRequester<Req, Item>, StreamingResponder<Req, Item>) {
+    streaming(None, response_capacity)
+}
@@ -0,0 +1,109 @@
+//! A blocking facade over the channel, for callers migrating from [`std::sync::mpsc`].
+//!
+//! Each blocking call drives its underlying future to completion on the current thread, using [`block_on`].
+//! **Do not call these from within an async context**: they will block the executor thread until they complete.
+//!
+//! ```
+//! use bidirectional_channel::sync::bounded;
+//! let (requester, responder) = bounded::<&str, usize>(1);
+//! let responder = std::thread::spawn(move || {
+//!     let request = responder.recv().unwrap();
+//!     let len = request.len();
+//!     request.respond(len).unwrap()
+//! });
+//! let response = requester.send("hello").unwrap().recv().unwrap();
+//! assert!(responder.join().unwrap().len() == response)
+//! ```
+
+use crate::{
+    ReceivedRequest, RecvError, Reply, Requester, Responder, ResponseError, SendRequestError,
+};
+use futures::{channel::oneshot, executor::block_on};
+use std::marker::PhantomData;
+
+/// A blocking [`Requester`]
+#[derive(Clone)]
+pub struct SyncRequester<Req, Resp> {
+    inner: Requester<Req, Resp>,
+}
+
+impl<Req, Resp> SyncRequester<Req, Resp> {
+    /// Put a request on the channel, blocking while the channel is full.
+    /// Returns a [`RecvResponse`], which blocks until the response arrives.
+    /// Fails if the [`SyncResponder`] was dropped, and returns your request back
+    pub fn send(&self, request: Req) -> Result<RecvResponse<Req, Resp>, SendRequestError<Req>> {
+        let response_receiver = block_on(self.inner.enqueue(request))?;
+        Ok(RecvResponse {
+            response_receiver,
+            request: PhantomData,
+        })
+    }
+
+    /// Recover the async [`Requester`]
+    pub fn into_inner(self) -> Requester<Req, Resp> {
+        self.inner
+    }
+}
+
+impl<Req, Resp> From<Requester<Req, Resp>> for SyncRequester<Req, Resp> {
+    fn from(inner: Requester<Req, Resp>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Handle to the response for a request sent by a [`SyncRequester`]
+#[must_use = "You must receive the response"]
+pub struct RecvResponse<Req, Resp> {
+    response_receiver: oneshot::Receiver<Reply<Resp>>,
+    request: PhantomData<fn() -> Req>,
+}
+
+impl<Req, Resp> RecvResponse<Req, Resp> {
+    /// Block until the response arrives.
+    /// Fails if the [`UnRespondedRequest`](crate::UnRespondedRequest) was dropped
+    pub fn recv(self) -> Result<Resp, SendRequestError<Req>> {
+        block_on(self.response_receiver)
+            .map_err(|_| ResponseError::Ignored)
+            .and_then(Reply::into_response)
+            .map_err(SendRequestError::from)
+    }
+}
+
+/// A blocking [`Responder`]
+#[derive(Clone)]
+pub struct SyncResponder<Req, Resp> {
+    inner: Responder<Req, Resp>,
+}
+
+impl<Req, Resp> SyncResponder<Req, Resp> {
+    /// Block until a request arrives.
+    /// Fails if every [`SyncRequester`] was dropped, and the channel is empty
+    pub fn recv(&self) -> Result<ReceivedRequest<Req, Resp>, RecvError> {
+        block_on(self.inner.recv())
+    }
+
+    /// Recover the async [`Responder`]
+    pub fn into_inner(self) -> Responder<Req, Resp> {
+        self.inner
+    }
+}
+
+impl<Req, Resp> From<Responder<Req, Resp>> for SyncResponder<Req, Resp> {
+    fn from(inner: Responder<Req, Resp>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Create a bounded [`SyncRequester`]-[`SyncResponder`] pair.
+/// See [`crate::bounded`]
+pub fn bounded<Req, Resp>(capacity: usize) -> (SyncRequester<Req, Resp>, SyncResponder<Req, Resp>) {
+    let (requester, responder) = crate::bounded(capacity);
+    (requester.into(), responder.into())
+}
+
+/// Create an unbounded [`SyncRequester`]-[`SyncResponder`] pair.
+/// See [`crate::unbounded`]
+pub fn unbounded<Req, Resp>() -> (SyncRequester<Req, Resp>, SyncResponder<Req, Resp>) {
+    let (requester, responder) = crate::unbounded();
+    (requester.into(), responder.into())
+}
@@ -0,0 +1,99 @@
+use crate::{lock, ReceivedRequest, UnRespondedRequest};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// An opaque stand-in for an [`UnRespondedRequest`], which can be sent across a process boundary (e.g. serialized with the `serde` feature).
+/// Redeem it with the [`Tickets`] which issued it, to respond to the original [`Requester`](crate::Requester).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ResponseTicket(u64);
+
+/// Holds the [`UnRespondedRequest`]s behind [`ResponseTicket`]s, e.g. in a broker which forwards requests to another process.
+/// Clones share the same tickets.
+///
+/// ```
+/// use bidirectional_channel::{bounded, Tickets};
+/// # use futures::join;
+/// # async_std::task::block_on(async {
+/// let (requester, responder) = bounded::<String, usize>(1);
+/// let tickets = Tickets::new();
+/// let (response, received) = join!(requester.request(String::from("hello")), responder.recv());
+/// // Forward the request and ticket to the far side, and get the response back with the ticket
+/// let (request, ticket) = received.unwrap().into_parts(&tickets);
+/// let len = request.len();
+/// tickets.redeem(ticket).unwrap().respond(len).unwrap();
+/// assert!(response.unwrap().await == Ok(5));
+/// # })
+/// ```
+pub struct Tickets<Resp> {
+    shared: Arc<Shared<Resp>>,
+}
+
+struct Shared<Resp> {
+    pending: Mutex<HashMap<ResponseTicket, UnRespondedRequest<Resp>>>,
+    next: AtomicU64,
+}
+
+impl<Resp> Clone for Tickets<Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<Resp> Default for Tickets<Resp> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Resp> Tickets<Resp> {
+    /// Create an empty set of tickets
+    pub fn new() -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                pending: Mutex::new(HashMap::new()),
+                next: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Hold on to `unresponded`, returning a ticket to redeem it later
+    pub fn issue(&self, unresponded: UnRespondedRequest<Resp>) -> ResponseTicket {
+        let ticket = ResponseTicket(self.shared.next.fetch_add(1, Ordering::Relaxed));
+        lock(&self.shared.pending).insert(ticket, unresponded);
+        ticket
+    }
+
+    /// Take back the [`UnRespondedRequest`] behind `ticket`, so it can be responded to.
+    /// Returns `None` if the ticket was already redeemed, or wasn't issued by these [`Tickets`]
+    pub fn redeem(&self, ticket: ResponseTicket) -> Option<UnRespondedRequest<Resp>> {
+        lock(&self.shared.pending).remove(&ticket)
+    }
+
+    /// How many tickets are waiting to be redeemed
+    pub fn len(&self) -> usize {
+        lock(&self.shared.pending).len()
+    }
+
+    /// Whether every ticket has been redeemed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<Req, Resp> ReceivedRequest<Req, Resp> {
+    /// Split off the request, leaving the [`UnRespondedRequest`] with `tickets`.
+    /// The returned [`ResponseTicket`] can travel with the request, and be redeemed with [`Tickets::redeem`] to respond
+    pub fn into_parts(self, tickets: &Tickets<Resp>) -> (Req, ResponseTicket) {
+        let (request, unresponded) = self.into();
+        (request, tickets.issue(unresponded))
+    }
+}
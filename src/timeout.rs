@@ -0,0 +1,112 @@
+use crate::{MappedRequester, Requester, Responder, SendFuture, SendRequestError};
+use std::time::Duration;
+
+/// A [`Requester`] which applies the same timeout to every request, created by [`Requester::timeout`],
+/// [`bounded_with_timeout`] or [`unbounded_with_timeout`].
+/// Clones share the same timeout
+pub struct TimeoutRequester<Req, Resp> {
+    inner: Requester<Req, Resp>,
+    timeout: Duration,
+}
+
+impl<Req, Resp> Clone for TimeoutRequester<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+impl<Req, Resp> Requester<Req, Resp> {
+    /// Give up on each request if the round trip takes longer than `timeout`.
+    /// The timer doesn't depend on any runtime
+    ///
+    /// ```
+    /// # use futures::join;
+    /// # async_std::task::block_on(async {
+    /// use std::time::Duration;
+    /// let (requester, responder) = bidirectional_channel::bounded::<&str, usize>(1);
+    /// let requester = requester.timeout(Duration::from_millis(50));
+    /// let (response, _ignored) = join!(requester.send("hello"), responder.recv());
+    /// assert!(response == Err(bidirectional_channel::SendRequestError::ResponseTimedOut));
+    /// # })
+    /// ```
+    pub fn timeout(self, timeout: Duration) -> TimeoutRequester<Req, Resp> {
+        TimeoutRequester {
+            inner: self,
+            timeout,
+        }
+    }
+}
+
+impl<Req, Resp> TimeoutRequester<Req, Resp> {
+    /// Make a request, giving up if the round trip takes longer than the channel's timeout.
+    /// Fails like [`Requester::send_timeout`]
+    pub async fn send(&self, request: Req) -> Result<Resp, SendRequestError<Req>> {
+        self.inner.send_timeout(request, self.timeout).await
+    }
+
+    /// Make a request with its own `timeout`, which replaces the channel's timeout rather than adding to it.
+    /// Fails like [`Requester::send_timeout`]
+    pub async fn send_timeout(
+        &self,
+        request: Req,
+        timeout: Duration,
+    ) -> Result<Resp, SendRequestError<Req>> {
+        self.inner.send_timeout(request, timeout).await
+    }
+
+    /// Make a request which waits as long as it takes.
+    /// See [`Requester::send`]
+    pub fn send_no_timeout(&self, request: Req) -> SendFuture<'_, Req, Resp> {
+        self.inner.send(request)
+    }
+
+    /// Convert each request with `f` before it's sent, keeping the timeout.
+    /// See [`Requester::map_request`]
+    pub fn map_request<OuterReq, F>(self, f: F) -> MappedRequester<OuterReq, Resp, Req, Resp>
+    where
+        F: Fn(OuterReq) -> Req + Send + Sync + 'static,
+    {
+        self.inner.map_request(f).timeout(self.timeout)
+    }
+
+    /// Convert each response with `f` once it's received, keeping the timeout.
+    /// See [`Requester::map_response`]
+    pub fn map_response<OuterResp, F>(self, f: F) -> MappedRequester<Req, OuterResp, Req, Resp>
+    where
+        F: Fn(Resp) -> OuterResp + Send + Sync + 'static,
+    {
+        self.inner.map_response(f).timeout(self.timeout)
+    }
+
+    /// The timeout applied by [`TimeoutRequester::send`]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Recover the underlying [`Requester`], whose requests don't time out
+    pub fn into_inner(self) -> Requester<Req, Resp> {
+        self.inner
+    }
+}
+
+/// Create a bounded [`TimeoutRequester`]-[`Responder`] pair, whose requests time out after `timeout`.
+/// See [`crate::bounded`]
+pub fn bounded_with_timeout<Req, Resp>(
+    capacity: usize,
+    timeout: Duration,
+) -> (TimeoutRequester<Req, Resp>, Responder<Req, Resp>) {
+    let (inner, responder) = crate::bounded(capacity);
+    (TimeoutRequester { inner, timeout }, responder)
+}
+
+/// Create an unbounded [`TimeoutRequester`]-[`Responder`] pair, whose requests time out after `timeout`.
+/// See [`crate::unbounded`]
+pub fn unbounded_with_timeout<Req, Resp>(
+    timeout: Duration,
+) -> (TimeoutRequester<Req, Resp>, Responder<Req, Resp>) {
+    let (inner, responder) = crate::unbounded();
+    (TimeoutRequester { inner, timeout }, responder)
+}
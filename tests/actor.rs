@@ -0,0 +1,61 @@
+use async_std::test;
+use bidirectional_channel::{
+    actor::{self, Actor},
+    bounded,
+    spawn::AsyncStd,
+};
+use futures::join;
+
+enum Command {
+    Increment,
+    Get,
+}
+
+#[derive(Default)]
+struct Counter {
+    count: usize,
+}
+
+impl Actor for Counter {
+    type Message = Command;
+    type Reply = usize;
+    async fn handle(&mut self, msg: Command) -> usize {
+        if let Command::Increment = msg {
+            self.count += 1
+        }
+        self.count
+    }
+}
+
+#[test]
+async fn spawned_counter() {
+    let counter = actor::spawn(Counter::default(), 1, AsyncStd);
+    for _ in 0..3 {
+        counter.send(Command::Increment).await.unwrap();
+    }
+    assert!(counter.send(Command::Get).await.unwrap() == 3);
+}
+
+#[test]
+async fn serve_stops_when_requesters_drop() {
+    let (requester, responder) = bounded(2);
+    let requests = async move {
+        join!(
+            requester.send(Command::Increment),
+            requester.send(Command::Increment)
+        )
+    };
+    let (_, counter) = join!(requests, actor::serve(Counter::default(), responder));
+    assert!(counter.count == 2);
+}
+
+#[test]
+async fn ask_and_tell() {
+    let counter = actor::spawn(Counter::default(), 4, AsyncStd);
+    let other = counter.clone();
+    for _ in 0..3 {
+        other.tell(Command::Increment).await.unwrap();
+    }
+    // Handled in order, so the increments come first
+    assert!(counter.ask(Command::Get).await.unwrap() == 3);
+}
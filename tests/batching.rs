@@ -0,0 +1,171 @@
+use async_std::test;
+use bidirectional_channel::{
+    batching::{BatchError, BatchingRequester},
+    bounded, Outcome, RequestMetrics, SendRequestError,
+};
+use futures::{future::join_all, join, poll};
+use ntest::timeout;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[test]
+#[timeout(1000)]
+async fn full_batches_are_sent_at_once() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let batching = BatchingRequester::new(requester, 3, Duration::from_secs(60));
+    let requests = join_all((0..3).map(|i| batching.send(i)));
+    let handler = async {
+        let batch = responder.recv().await.unwrap();
+        assert!(*batch == [0, 1, 2]);
+        let doubled = batch.iter().map(|i| i * 2).collect();
+        batch.respond(doubled).unwrap();
+    };
+    let (responses, ()) = join!(requests, handler);
+    let responses: Vec<_> = responses.into_iter().map(Result::unwrap).collect();
+    assert!(responses == [0, 2, 4]);
+}
+
+#[test]
+#[timeout(1000)]
+async fn partial_batches_are_sent_after_the_delay() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let batching = BatchingRequester::new(requester, 10, Duration::from_millis(20));
+    let handler = async {
+        let batch = responder.recv().await.unwrap();
+        assert!(*batch == [7]);
+        batch.respond(vec![8]).unwrap();
+    };
+    let (response, ()) = join!(batching.send(7), handler);
+    assert!(response.unwrap() == 8);
+    assert!(batching.is_empty());
+}
+
+#[test]
+#[timeout(1000)]
+async fn wrong_length_fails_every_caller() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let batching = BatchingRequester::new(requester, 2, Duration::from_secs(60));
+    let handler = async {
+        responder.recv().await.unwrap().respond(vec![0]).unwrap();
+    };
+    let ((first, second), ()) = join!(async { join!(batching.send(1), batching.send(2)) }, handler);
+    for response in [first, second] {
+        assert!(matches!(
+            response,
+            Err(BatchError::WrongLength {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+}
+
+#[test]
+#[timeout(1000)]
+async fn closed_channel_returns_each_request() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    drop(responder);
+    let batching = BatchingRequester::new(requester, 10, Duration::from_secs(60));
+    let (first, second, ()) = join!(batching.send(1), batching.send(2), batching.flush());
+    assert!(matches!(
+        first,
+        Err(BatchError::Send(SendRequestError::Closed(1)))
+    ));
+    assert!(matches!(
+        second,
+        Err(BatchError::Send(SendRequestError::Closed(2)))
+    ));
+}
+
+#[test]
+#[timeout(1000)]
+async fn cancelled_flush_is_taken_over_while_full() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let _filler = requester.try_send(vec![0]).unwrap();
+    let batching = BatchingRequester::new(requester, 2, Duration::from_secs(60));
+    let mut first = Box::pin(batching.send(1));
+    assert!(poll!(first.as_mut()).is_pending());
+    let mut second = Box::pin(batching.send(2));
+    // Sending the batch, but the channel is full
+    assert!(poll!(second.as_mut()).is_pending());
+    drop(second);
+    let handler = async {
+        drop(responder.recv().await.unwrap());
+        let batch = responder.recv().await.unwrap();
+        assert!(*batch == [1, 2]);
+        batch.respond(vec![10, 20]).unwrap();
+    };
+    let (first, ()) = join!(first, handler);
+    assert!(first.unwrap() == 10);
+}
+
+#[test]
+#[timeout(1000)]
+async fn cancelled_flush_is_taken_over_once_sent() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let batching = BatchingRequester::new(requester, 2, Duration::from_secs(60));
+    let mut first = Box::pin(batching.send(1));
+    assert!(poll!(first.as_mut()).is_pending());
+    let mut second = Box::pin(batching.send(2));
+    // The batch is on the channel, waiting for its responses
+    assert!(poll!(second.as_mut()).is_pending());
+    drop(second);
+    let handler = async {
+        let batch = responder.recv().await.unwrap();
+        assert!(!batch.unresponded.is_canceled());
+        batch.respond(vec![10, 20]).unwrap();
+    };
+    let (first, ()) = join!(first, handler);
+    assert!(first.unwrap() == 10);
+}
+
+#[test]
+#[timeout(1000)]
+async fn dropping_flushes_what_is_buffered() {
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let batching = BatchingRequester::new(requester, 10, Duration::from_secs(60));
+    // Give up waiting
+    assert!(poll!(Box::pin(batching.send(1))).is_pending());
+    assert!(batching.len() == 1);
+    drop(batching);
+    assert!(*responder.recv().await.unwrap() == [1]);
+}
+
+#[test]
+#[timeout(1000)]
+async fn dropping_while_full_drops_what_is_buffered() {
+    let (requester, responder) = bounded::<Vec<Arc<()>>, Vec<usize>>(1);
+    let _filler = requester.try_send(vec![]).unwrap();
+    let batching = BatchingRequester::new(requester, 10, Duration::from_secs(60));
+    let request = Arc::new(());
+    assert!(poll!(Box::pin(batching.send(request.clone()))).is_pending());
+    drop(batching);
+    assert!(Arc::strong_count(&request) == 1);
+    assert!(responder.recv().await.unwrap().is_empty());
+    assert!(responder.try_recv().is_err());
+}
+
+#[test]
+#[timeout(1000)]
+async fn waiting_for_space_measures_the_batch_once() {
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let (requester, responder) = bounded::<Vec<usize>, Vec<usize>>(1);
+    let requester = requester.with_metrics({
+        let reported = reported.clone();
+        move |metrics: RequestMetrics| reported.lock().unwrap().push(metrics.outcome)
+    });
+    let _filler = requester.try_send(vec![0]).unwrap();
+    let batching = BatchingRequester::new(requester, 1, Duration::from_secs(60));
+    let mut send = Box::pin(batching.send(1));
+    for _ in 0..3 {
+        assert!(poll!(send.as_mut()).is_pending());
+    }
+    assert!(reported.lock().unwrap().is_empty());
+    // Orphaned, then refused by the full channel on drop
+    drop(send);
+    drop(batching);
+    assert!(*reported.lock().unwrap() == [Outcome::Full]);
+    drop(responder);
+}
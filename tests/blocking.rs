@@ -0,0 +1,37 @@
+#![cfg(feature = "blocking")]
+use bidirectional_channel::{bounded, RecvError, SendRequestError};
+use std::thread;
+
+#[test]
+fn request_response() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let responder = thread::spawn(move || {
+        while let Ok(request) = responder.recv_blocking() {
+            let len = request.len();
+            request.respond(len).unwrap();
+        }
+    });
+    assert!(requester.send_blocking("hello").unwrap() == 5);
+    assert!(requester.send_blocking("world!").unwrap() == 6);
+    drop(requester);
+    // Stops receiving once the requester is gone
+    responder.join().unwrap();
+}
+
+#[test]
+fn recv_after_requester_dropped() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    drop(requester);
+    let responder = thread::spawn(move || responder.recv_blocking());
+    assert!(matches!(responder.join().unwrap(), Err(RecvError)));
+}
+
+#[test]
+fn closed() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    drop(responder);
+    assert!(matches!(
+        requester.send_blocking("hello"),
+        Err(SendRequestError::Closed("hello"))
+    ));
+}
@@ -0,0 +1,48 @@
+use async_std::test;
+use bidirectional_channel::{bounded, broadcast::Broadcaster, SendRequestError};
+use futures::{future::join_all, join};
+
+#[test]
+async fn every_backend_responds() {
+    let (requesters, responders): (Vec<_>, Vec<_>) =
+        (0..3).map(|_| bounded::<&str, usize>(1)).unzip();
+    let broadcaster = Broadcaster::new(requesters);
+    let handlers = join_all(
+        responders
+            .iter()
+            .enumerate()
+            .map(|(id, responder)| async move {
+                let request = responder.recv().await.unwrap();
+                request.respond(id).unwrap()
+            }),
+    );
+    let (responses, _) = join!(broadcaster.broadcast("ping"), handlers);
+    assert!(matches!(responses[..], [Ok(0), Ok(1), Ok(2)]));
+}
+
+#[test]
+async fn partial_failure() {
+    let (requesters, mut responders): (Vec<_>, Vec<_>) =
+        (0..3).map(|_| bounded::<&str, usize>(1)).unzip();
+    let broadcaster = Broadcaster::new(requesters);
+    drop(responders.remove(1));
+    let handlers = async {
+        drop(responders[0].recv().await.unwrap());
+        responders[1].recv().await.unwrap().respond(2).unwrap();
+    };
+    let (responses, _) = join!(broadcaster.broadcast("ping"), handlers);
+    assert!(matches!(
+        responses[..],
+        [
+            Err(SendRequestError::Ignored),
+            Err(SendRequestError::Closed("ping")),
+            Ok(2)
+        ]
+    ));
+}
+
+#[test]
+async fn empty() {
+    let broadcaster = Broadcaster::<&str, usize>::new(vec![]);
+    assert!(broadcaster.broadcast("ping").await.is_empty());
+}
@@ -0,0 +1,38 @@
+use async_std::test;
+use bidirectional_channel::bus::{Bus, BusError};
+use futures::join;
+
+#[test]
+async fn routes_by_subject() {
+    let bus = Bus::<&str, usize>::bounded(1);
+    let lengths = bus.responder("len");
+    let counts = bus.responder("count");
+    let handlers = async {
+        let request = counts.recv().await.unwrap();
+        let count = request.matches('l').count();
+        request.respond(count).unwrap();
+        let request = lengths.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap();
+    };
+    let requests = async {
+        assert!(bus.request("count", "hello").await.unwrap() == 2);
+        assert!(bus.request("len", "hello").await.unwrap() == 5);
+    };
+    join!(handlers, requests);
+}
+
+#[test]
+async fn no_responder() {
+    let bus = Bus::<&str, usize>::bounded(1);
+    assert!(matches!(
+        bus.request("len", "hello").await,
+        Err(BusError::NoResponder("hello"))
+    ));
+    drop(bus.responder("len"));
+    assert!(matches!(
+        bus.request("len", "hello").await,
+        Err(BusError::NoResponder("hello"))
+    ));
+    assert!(bus.subjects().is_empty());
+}
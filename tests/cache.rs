@@ -0,0 +1,106 @@
+use async_std::test;
+use bidirectional_channel::{
+    bounded,
+    cache::{CacheStats, CachedRequester},
+    Responder, SendRequestError,
+};
+use futures::{future::join_all, join};
+use ntest::timeout;
+use std::time::Duration;
+
+/// Respond to every request with its length, ignoring empty requests
+async fn worker(responder: Responder<&'static str, usize>) {
+    while let Ok(request) = responder.recv().await {
+        if !request.is_empty() {
+            let len = request.len();
+            let _ = request.respond(len);
+        }
+    }
+}
+
+#[test]
+#[timeout(1000)]
+async fn hits_and_expiry() {
+    let (requester, responder) = bounded(1);
+    async_std::task::spawn(worker(responder));
+    let requester = CachedRequester::new(requester, Duration::from_millis(50));
+    assert!(requester.send("hello").await.unwrap() == 5);
+    assert!(requester.send("hello").await.unwrap() == 5);
+    assert!(requester.stats() == CacheStats { hits: 1, misses: 1 });
+    async_std::task::sleep(Duration::from_millis(100)).await;
+    assert!(requester.send("hello").await.unwrap() == 5);
+    requester.invalidate(&"hello");
+    assert!(requester.send("hello").await.unwrap() == 5);
+    assert!(requester.stats() == CacheStats { hits: 1, misses: 3 });
+}
+
+#[test]
+#[timeout(1000)]
+async fn concurrent_misses_coalesce() {
+    let (requester, responder) = bounded::<&str, usize>(4);
+    let requester = CachedRequester::new(requester, Duration::from_secs(60));
+    let requests = join_all((0..4).map(|_| requester.send("hello")));
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        // Every other request is waiting on this one
+        assert!(responder.is_empty());
+        request.respond(5).unwrap();
+    };
+    let (responses, _) = join!(requests, handler);
+    assert!(responses.into_iter().all(|response| response.unwrap() == 5));
+    assert!(requester.stats() == CacheStats { hits: 3, misses: 1 });
+}
+
+#[test]
+#[timeout(1000)]
+async fn errors_are_not_cached() {
+    let (requester, responder) = bounded(1);
+    async_std::task::spawn(worker(responder));
+    let requester = CachedRequester::new(requester, Duration::from_secs(60));
+    assert!(matches!(
+        requester.send("").await,
+        Err(SendRequestError::Ignored)
+    ));
+    assert!(matches!(
+        requester.send("").await,
+        Err(SendRequestError::Ignored)
+    ));
+    assert!(requester.stats() == CacheStats { hits: 0, misses: 2 });
+}
+
+#[test]
+#[timeout(1000)]
+async fn invalidated_while_in_flight() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let requester = CachedRequester::new(requester, Duration::from_secs(60));
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        requester.invalidate(&"hello");
+        request.respond(5).unwrap();
+    };
+    let (response, ()) = join!(requester.send("hello"), handler);
+    assert!(response.unwrap() == 5);
+    // The stale response wasn't cached
+    assert!(requester.is_empty());
+    let (response, _) = join!(requester.send("hello"), async {
+        responder.recv().await.unwrap().respond(6).unwrap()
+    });
+    assert!(response.unwrap() == 6);
+    assert!(requester.stats() == CacheStats { hits: 0, misses: 2 });
+}
+
+#[test]
+#[timeout(1000)]
+async fn expired_entries_are_purged() {
+    let (requester, responder) = bounded(1);
+    async_std::task::spawn(worker(responder));
+    let requester = CachedRequester::new(requester, Duration::from_millis(20));
+    for request in ["a", "bb", "ccc"] {
+        requester.send(request).await.unwrap();
+    }
+    assert!(requester.len() == 3);
+    async_std::task::sleep(Duration::from_millis(50)).await;
+    // Never requested again, but purged by the next miss
+    requester.send("dddd").await.unwrap();
+    assert!(requester.len() == 1);
+}
@@ -0,0 +1,49 @@
+use async_std::test;
+use bidirectional_channel::{
+    duplex::{self, ExchangeError, SendError},
+    RecvError,
+};
+use futures::{join, StreamExt};
+
+#[test]
+async fn ping_pong() {
+    let (left, right) = duplex::bounded::<usize, usize>(1);
+    let left = async move {
+        let mut ball = 0;
+        while ball < 10 {
+            ball = left.exchange(ball + 1).await.unwrap();
+        }
+        ball
+    };
+    let right = async move {
+        while let Ok(ball) = right.recv().await {
+            if right.send(ball + 1).await.is_err() {
+                break;
+            }
+        }
+    };
+    let (ball, ()) = join!(left, right);
+    assert!(ball == 10);
+}
+
+#[test]
+async fn independent_directions() {
+    let (left, right) = duplex::unbounded::<&str, usize>();
+    left.send("one").await.unwrap();
+    left.send("two").await.unwrap();
+    right.send(1).await.unwrap();
+    drop(left);
+    // Both of the left's messages are still there after it was dropped
+    assert!(right.clone().collect::<Vec<_>>().await == ["one", "two"]);
+    assert!(matches!(right.send(2).await, Err(SendError::Closed(2))));
+    assert!(right.recv().await == Err(RecvError));
+}
+
+#[test]
+async fn hung_up() {
+    let (left, right) = duplex::bounded::<&str, usize>(1);
+    let (reply, ()) = join!(left.exchange("hello"), async move {
+        right.recv().await.unwrap();
+    });
+    assert!(matches!(reply, Err(ExchangeError::HungUp)));
+}
@@ -0,0 +1,109 @@
+use async_std::test;
+use bidirectional_channel::{bounded, idempotency::IdempotencyConfig, ResponderExt};
+use futures::{join, poll};
+use ntest::timeout;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+#[test]
+#[timeout(1000)]
+async fn retried_token_is_not_handled_again() {
+    let (requester, responder) = bounded::<&str, usize>(4);
+    let responder = responder.idempotent(IdempotencyConfig::default());
+    let handled = AtomicUsize::new(0);
+    let handler = async {
+        while let Ok(request) = responder.recv().await {
+            let handled = handled.fetch_add(1, Ordering::SeqCst);
+            request.respond(handled).unwrap();
+        }
+    };
+    let requests = async {
+        let first = requester.send_idempotent(7, "hello").await.unwrap();
+        let retry = requester.send_idempotent(7, "hello").await.unwrap();
+        let other = requester.send_idempotent(8, "hello").await.unwrap();
+        let untagged = requester.send("hello").await.unwrap();
+        drop(requester);
+        (first, retry, other, untagged)
+    };
+    let (_, responses) = join!(handler, requests);
+    assert!(responses == (0, 0, 1, 2), "{:?}", responses);
+    assert!(handled.load(Ordering::SeqCst) == 3);
+}
+
+#[test]
+#[timeout(1000)]
+async fn duplicates_in_flight_share_the_response() {
+    let (requester, responder) = bounded::<&str, usize>(4);
+    let responder = responder.idempotent(IdempotencyConfig::default());
+    let requests = async {
+        join!(
+            requester.send_idempotent(7, "a"),
+            requester.send_idempotent(7, "a")
+        )
+    };
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        // The duplicate arrives while we're working
+        let mut next = Box::pin(responder.recv());
+        while !requester.is_empty() {
+            assert!(poll!(next.as_mut()).is_pending());
+            async_std::task::yield_now().await
+        }
+        request.respond(1).unwrap();
+    };
+    let ((first, second), _) = join!(requests, handler);
+    assert!(matches!((first, second), (Ok(1), Ok(1))));
+}
+
+#[test]
+#[timeout(1000)]
+async fn retry_after_the_first_requester_gave_up() {
+    let (requester, responder) = bounded::<&str, usize>(4);
+    let responder = responder.idempotent(IdempotencyConfig::default());
+    let mut first = Box::pin(requester.send_idempotent(7, "a"));
+    assert!(poll!(first.as_mut()).is_pending());
+    let request = responder.recv().await.unwrap();
+    // e.g. timed out
+    drop(first);
+    assert!(request.unresponded.is_canceled());
+    let handler = async {
+        // The retry arrives while we're working
+        let mut next = Box::pin(responder.recv());
+        while !requester.is_empty() {
+            assert!(poll!(next.as_mut()).is_pending());
+            async_std::task::yield_now().await
+        }
+        assert!(request.respond(1).is_err());
+    };
+    let (retry, _) = join!(requester.send_idempotent(7, "a"), handler);
+    assert!(matches!(retry, Ok(1)));
+}
+
+#[test]
+#[timeout(1000)]
+async fn expiry() {
+    let (requester, responder) = bounded::<&str, usize>(4);
+    let responder = responder.idempotent(IdempotencyConfig {
+        capacity: 1,
+        ttl: Duration::from_millis(50),
+    });
+    let handler = async {
+        let mut handled = 0;
+        while let Ok(request) = responder.recv().await {
+            handled += 1;
+            request.respond(handled).unwrap();
+        }
+    };
+    let requests = async {
+        assert!(requester.send_idempotent(1, "a").await.unwrap() == 1);
+        // Evicts token 1
+        assert!(requester.send_idempotent(2, "a").await.unwrap() == 2);
+        assert!(requester.send_idempotent(1, "a").await.unwrap() == 3);
+        async_std::task::sleep(Duration::from_millis(100)).await;
+        assert!(requester.send_idempotent(1, "a").await.unwrap() == 4);
+        drop(requester);
+    };
+    join!(handler, requests);
+}
@@ -1,7 +1,17 @@
 use async_std::test;
-use bidirectional_channel::{bounded, SendRequestError};
-use futures::join;
+use bidirectional_channel::{
+    bounded, bounded_named, bounded_with_ids, bounded_with_meta, bounded_with_policy,
+    bounded_with_timeout, rendezvous, select_any, unbounded, EnqueueError, Outcome, Overflow,
+    PendingResponses, ReceivedRequest, RecvError, RequestError, RequestId, RequestMetrics,
+    Requester, RespondOutcome, Responder, ResponderExt, ResponseError, SendFuture,
+    SendRequestError, TryRecvError, TrySendError, UnRespondedRequest,
+};
+use futures::{future::join_all, join, poll, stream::FusedStream, SinkExt, StreamExt};
 use ntest::timeout;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 #[test]
 async fn request_response() {
@@ -59,3 +69,1643 @@ async fn deadlock() {
     };
     join!(requester, responder);
 }
+
+#[test]
+async fn respond_confirmed() {
+    let (requester, responder) = bounded(1);
+    let (response, request) = join!(requester.send("hello"), async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond_confirmed(len).await.unwrap()
+    });
+    assert!(request.len() == response.unwrap())
+}
+
+#[test]
+async fn respond_confirmed_abandoned() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let mut send = Box::pin(requester.send("hello"));
+    assert!(poll!(&mut send).is_pending());
+    let request = responder.recv().await.unwrap();
+    // The response is delivered, but the requester goes away before taking it
+    let mut confirmed = Box::pin(request.respond_confirmed(5));
+    assert!(poll!(&mut confirmed).is_pending());
+    drop(send);
+    assert!(matches!(confirmed.await, Err(("hello", 5))))
+}
+
+#[test]
+async fn respond_confirmed_split() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (response, confirmed) = join!(requester.send("hello"), async {
+        let (request, unresponded) = responder.recv().await.unwrap().split();
+        unresponded.respond_confirmed(request.len()).await
+    });
+    assert!(response.unwrap() == 5 && confirmed == Ok(()));
+
+    let response = requester.request("hello").await.unwrap();
+    let (_, unresponded) = responder.recv().await.unwrap().split();
+    let mut confirmed = Box::pin(unresponded.respond_confirmed(5));
+    assert!(poll!(&mut confirmed).is_pending());
+    drop(response);
+    assert!(confirmed.await == Err(5));
+}
+
+#[test]
+async fn send_detailed() {
+    let (requester, responder) = bounded(1);
+    let (timings, _) = join!(requester.send_detailed("hello"), async {
+        let request = responder.recv_stamped().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(20)).await;
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    let timings = timings.unwrap();
+    assert!(timings.response == 5);
+    assert!(timings.queue_time.is_some());
+    assert!(timings.processing_time.unwrap() >= Duration::from_millis(20))
+}
+
+#[test]
+async fn send_detailed_unstamped() {
+    let (requester, responder) = bounded(1);
+    let (timings, _) = join!(requester.send_detailed("hello"), async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    let timings = timings.unwrap();
+    assert!(timings.queue_time.is_none() && timings.processing_time.is_none())
+}
+
+#[test]
+async fn merge() {
+    let (requesters, responders): (Vec<_>, Vec<_>) =
+        (0..3).map(|_| bounded::<&str, usize>(1)).unzip();
+    let handler = async {
+        let mut merged = Responder::merge(responders);
+        while let Some(request) = merged.next().await {
+            let len = request.len();
+            request.respond(len).unwrap();
+        }
+    };
+    let requesters = async {
+        let responses = join_all(
+            requesters
+                .iter()
+                .zip(["a", "bb", "ccc"])
+                .map(|(requester, request)| requester.send(request)),
+        )
+        .await;
+        drop(requesters);
+        responses
+    };
+    let (responses, _) = join!(requesters, handler);
+    let responses: Vec<_> = responses.into_iter().map(Result::unwrap).collect();
+    assert!(responses == [1, 2, 3])
+}
+
+#[test]
+async fn deadline_propagates() {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let (front, front_responder) = bounded::<&str, usize>(1);
+    let (back, back_responder) = bounded::<&str, usize>(1);
+    let front_handler = async {
+        let request = front_responder.recv().await.unwrap();
+        assert!(request.deadline() == Some(deadline));
+        let len = back
+            .send_before(*request, request.deadline())
+            .await
+            .unwrap();
+        request.respond(len).unwrap();
+    };
+    let back_handler = async {
+        let request = back_responder.recv().await.unwrap();
+        assert!(request.deadline() == Some(deadline));
+        assert!(request.remaining().unwrap() <= Duration::from_secs(10));
+        let len = request.len();
+        request.respond(len).unwrap();
+    };
+    let (response, _, _) = join!(
+        front.send_before("hello", deadline),
+        front_handler,
+        back_handler
+    );
+    assert!(response.unwrap() == 5)
+}
+
+#[test]
+async fn recv_limited() {
+    let (requester, responder) = bounded::<&str, usize>(2);
+    let pending = PendingResponses::default();
+    let handler = async {
+        let first = responder.recv_limited(1, &pending).await.unwrap();
+        assert!(pending.count() == 1);
+        let mut second = Box::pin(responder.recv_limited(1, &pending));
+        assert!(poll!(&mut second).is_pending());
+        // Still on the channel, applying backpressure
+        assert!(responder.len() == 1);
+        let len = first.len();
+        first.respond(len).unwrap();
+        let second = second.await.unwrap();
+        let len = second.len();
+        second.respond(len).unwrap();
+    };
+    let (first, second, _) = join!(requester.send("a"), requester.send("bb"), handler);
+    assert!(first.unwrap() == 1 && second.unwrap() == 2);
+    assert!(pending.count() == 0)
+}
+
+#[test]
+async fn send_and_forget() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    requester.send_and_forget("hello").await.unwrap();
+    let request = responder.recv().await.unwrap();
+    assert!(matches!(request.respond(5), Err(("hello", 5))));
+    drop(responder);
+    assert!(matches!(
+        requester.send_and_forget("hello").await,
+        Err(EnqueueError::Closed("hello"))
+    ))
+}
+
+#[test]
+async fn respond_if_waiting_delivered() {
+    let (requester, responder) = bounded(1);
+    let (response, outcome) = join!(requester.send("hello"), async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond_if_waiting(len)
+    });
+    assert!(matches!(outcome, RespondOutcome::Delivered("hello")));
+    assert!(response.unwrap() == 5)
+}
+
+#[test]
+async fn respond_if_waiting_skipped() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    requester.send_and_forget("hello").await.unwrap();
+    let request = responder.recv().await.unwrap();
+    assert!(matches!(
+        request.respond_if_waiting(5),
+        RespondOutcome::SkippedGone("hello", 5)
+    ))
+}
+
+#[test]
+async fn coalesce_by() {
+    let (requester, responder) = bounded::<&str, usize>(3);
+    let handler = async {
+        let mut groups =
+            responder.coalesce_by(|request| request.as_bytes()[0], Duration::from_millis(20));
+        let mut sizes = Vec::new();
+        while let Some(group) = groups.next().await {
+            sizes.push(group.len());
+            for request in group {
+                let len = request.len();
+                request.respond(len).unwrap();
+            }
+        }
+        sizes
+    };
+    let requests = async {
+        let responses = join!(
+            requester.send("apple"),
+            requester.send("banana"),
+            requester.send("avocado")
+        );
+        drop(requester);
+        responses
+    };
+    let ((apple, banana, avocado), sizes) = join!(requests, handler);
+    assert!(sizes == [2, 1]);
+    assert!(apple.unwrap() == 5 && banana.unwrap() == 6 && avocado.unwrap() == 7)
+}
+
+#[test]
+async fn named_send_future() {
+    struct InFlight<'a> {
+        response: SendFuture<'a, &'static str, usize>,
+    }
+    let (requester, responder) = bounded(1);
+    let in_flight = InFlight {
+        response: requester.send("hello"),
+    };
+    let (response, _) = join!(in_flight.response, async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    assert!(response.unwrap() == 5)
+}
+
+#[test]
+#[timeout(1000)]
+async fn validated() {
+    let (requester, responder) = bounded::<&str, Result<usize, &str>>(2);
+    let responder = responder.validated(|request| match request.is_empty() {
+        true => Err(Err("empty")),
+        false => Ok(()),
+    });
+    let handler = async_std::task::spawn(async move {
+        while let Ok(request) = responder.recv().await {
+            assert!(!request.is_empty());
+            let len = request.len();
+            request.respond(Ok(len)).unwrap();
+        }
+    });
+    assert!(matches!(requester.send("").await, Ok(Err("empty"))));
+    assert!(matches!(requester.send("hello").await, Ok(Ok(5))));
+    drop(requester);
+    handler.await
+}
+
+#[test]
+#[timeout(1000)]
+async fn take_pending_and_inject() {
+    let (requester, old_responder) = bounded::<&str, usize>(2);
+    let (replacement, new_responder) = bounded::<&str, usize>(2);
+    let in_flight = join_all(vec![requester.send("hello"), requester.send("world!")]);
+    let migrate = async {
+        while requester.len() < 2 {
+            async_std::task::yield_now().await
+        }
+        let pending = old_responder.take_pending();
+        assert!(pending.len() == 2);
+        assert!(replacement.inject(pending).await.is_ok());
+        for _ in 0..2 {
+            let request = new_responder.recv().await.unwrap();
+            let len = request.len();
+            request.respond(len).unwrap();
+        }
+    };
+    let (responses, _) = join!(in_flight, migrate);
+    assert!(matches!(responses[..], [Ok(5), Ok(6)]));
+}
+
+#[test]
+#[timeout(2000)]
+async fn throttled() {
+    let (requester, responder) = bounded::<(), ()>(5);
+    for _ in 0..5 {
+        requester.send_and_forget(()).await.unwrap();
+    }
+    let mut responder = responder.throttled(20.0).burst(2);
+    let start = Instant::now();
+    for _ in 0..5 {
+        drop(responder.next().await.unwrap());
+    }
+    // Two in the burst, then one every 50ms
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(140), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(500), "{:?}", elapsed);
+}
+
+#[test]
+#[timeout(1000)]
+async fn send_shared() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let response = requester.send_shared("hello").await.unwrap();
+    let consumers = join_all((0..3).map(|_| response.clone()));
+    let request = responder.recv().await.unwrap();
+    let len = request.len();
+    request.respond(len).unwrap();
+    assert!(consumers.await == [Ok(5); 3]);
+    assert!(response.await == Ok(5));
+
+    let response = requester.send_shared("hello").await.unwrap();
+    drop(responder.recv().await.unwrap());
+    assert!(response.await == Err(ResponseError::Ignored));
+}
+
+#[test]
+#[timeout(1000)]
+async fn owed_stream() {
+    let (requester, responder) = bounded::<&str, usize>(3);
+    for request in ["a", "b", "c"].iter() {
+        requester.send_and_forget(*request).await.unwrap();
+    }
+    let mut responder = responder.into_owed_stream();
+    let owed = responder.owed();
+    let a = responder.next().await.unwrap();
+    let b = responder.next().await.unwrap();
+    let c = responder.next().await.unwrap();
+    assert!(owed.count() == 3);
+    let _ = b.respond(1);
+    drop(a);
+    let still_owed = owed.list();
+    assert!(still_owed.len() == 1 && still_owed[0].id == 2);
+    let _ = c.respond(1);
+    assert!(owed.count() == 0);
+}
+
+#[test]
+#[timeout(1000)]
+async fn send_timeout_response() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let slow = async {
+        let request = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(100)).await;
+        request.respond(5)
+    };
+    let (response, late) = join!(
+        requester.send_timeout("hello", Duration::from_millis(20)),
+        slow
+    );
+    assert!(matches!(response, Err(SendRequestError::ResponseTimedOut)));
+    assert!(matches!(late, Err(("hello", 5))));
+}
+
+#[test]
+#[timeout(1000)]
+async fn send_timeout_full() {
+    let (requester, _responder) = bounded::<&str, usize>(1);
+    requester.send_and_forget("first").await.unwrap();
+    assert!(matches!(
+        requester
+            .send_timeout("second", Duration::from_millis(20))
+            .await,
+        Err(SendRequestError::TimedOut("second"))
+    ));
+}
+
+#[test]
+#[timeout(1000)]
+async fn send_timeout_enqueues_as_soon_as_there_is_space() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    requester.send_and_forget("first").await.unwrap();
+    let send = requester.send_timeout("second", Duration::from_secs(5));
+    futures::pin_mut!(send);
+    assert!(poll!(&mut send).is_pending());
+    drop(responder.recv().await.unwrap());
+    // Woken by the space being made, rather than a timer
+    assert!(poll!(&mut send).is_pending());
+    let second = responder.try_recv().unwrap();
+    assert!(*second == "second");
+    second.respond(6).unwrap();
+    assert!(send.await == Ok(6));
+}
+
+#[test]
+async fn try_send() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let response = requester.try_send("hello").unwrap();
+    assert!(matches!(
+        requester.try_send("world"),
+        Err(TrySendError::Full("world"))
+    ));
+    let request = responder.recv().await.unwrap();
+    let len = request.len();
+    request.respond(len).unwrap();
+    assert!(response.await == Ok(5));
+
+    let response = requester.try_send("hello").unwrap();
+    drop(responder.recv().await.unwrap());
+    assert!(matches!(
+        response.await.map_err(SendRequestError::<&str>::from),
+        Err(SendRequestError::Ignored)
+    ));
+
+    drop(responder);
+    assert!(matches!(
+        requester.try_send("hello"),
+        Err(TrySendError::Closed("hello"))
+    ));
+}
+
+#[test]
+async fn request_out_of_order() {
+    let (requester, responder) = bounded::<&str, usize>(2);
+    let first = requester.request("a").await.unwrap();
+    let second = requester.request("bb").await.unwrap();
+    for _ in 0..2 {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap();
+    }
+    assert!(second.await == Ok(2));
+    assert!(first.await == Ok(1));
+}
+
+#[test]
+async fn requester_introspection() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    assert!(requester.capacity() == Some(1));
+    assert!(unbounded::<(), ()>().0.capacity().is_none());
+    requester.send_and_forget("hello").await.unwrap();
+    assert!(requester.is_full() && requester.len() == 1);
+    assert!(requester.close());
+    assert!(!requester.close() && requester.is_closed());
+    assert!(matches!(
+        requester.send_and_forget("world").await,
+        Err(EnqueueError::Closed("world"))
+    ));
+    // Requests already on the channel are drained first
+    assert!(*responder.recv().await.unwrap() == "hello");
+    assert!(responder.recv().await.is_err());
+}
+
+#[test]
+async fn responder_accessors() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    assert!(matches!(responder.try_recv(), Err(TryRecvError::Empty)));
+    requester.send_and_forget("hello").await.unwrap();
+    assert!(responder.is_full() && responder.capacity() == Some(1));
+    assert!(responder.close());
+    assert!(*responder.try_recv().unwrap() == "hello");
+    assert!(matches!(responder.try_recv(), Err(TryRecvError::Closed)));
+    assert!(matches!(responder.recv().await, Err(RecvError)));
+}
+
+#[test]
+#[timeout(1000)]
+async fn responder_stream() {
+    let (requester, responder) = bounded::<usize, usize>(4);
+    let requests = async {
+        let responses = join_all((0..8).map(|i| requester.send(i))).await;
+        drop(requester);
+        responses
+    };
+    // Ends once every requester is dropped
+    let worker = responder.for_each_concurrent(4, |request| async move {
+        let doubled = *request * 2;
+        request.respond(doubled).unwrap();
+    });
+    let (responses, _) = join!(requests, worker);
+    let responses = responses
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(responses == [0, 2, 4, 6, 8, 10, 12, 14]);
+}
+
+#[test]
+async fn weak_requester() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let weak = requester.downgrade();
+    let upgraded = weak.upgrade().unwrap();
+    upgraded.send_and_forget("hello").await.unwrap();
+    drop((requester, upgraded));
+    assert!(weak.upgrade().is_none());
+    // Drains, then sees the channel close
+    assert!(*responder.recv().await.unwrap() == "hello");
+    assert!(responder.recv().await.is_err());
+}
+
+#[test]
+async fn respond_with() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let (response, request) = join!(requester.send("hello"), async {
+        responder
+            .recv()
+            .await
+            .unwrap()
+            .respond_with(|request| request.len())
+    });
+    assert!(matches!((response, request), (Ok(5), Ok("hello"))));
+
+    requester.send_and_forget("hello").await.unwrap();
+    let request = responder.recv().await.unwrap();
+    assert!(matches!(
+        request.respond_with(|request| request.len()),
+        Err(("hello", 5))
+    ));
+}
+
+#[test]
+async fn respond_ref() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let (response, _) = join!(requester.send("hello"), async {
+        let mut request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond_ref(len).unwrap();
+        // Still borrowable, but spent
+        assert!(*request == "hello");
+        assert!(request.respond_ref(len) == Err(5));
+    });
+    assert!(matches!(response, Ok(5)));
+}
+
+#[test]
+async fn request_sink_closed() {
+    let (requester, responder) = bounded::<usize, ()>(1);
+    drop(responder);
+    let mut sink = requester.into_sink();
+    sink.send(1).await.unwrap_err();
+}
+
+#[test]
+async fn mapped_requester() {
+    let (requester, responder) = bounded::<String, usize>(1);
+    let requester = requester
+        .map_request(|request: String| request.to_uppercase())
+        .map_response(|len| len * 2)
+        .map_request(|request: char| request.to_string().repeat(3));
+    let (response, _) = join!(requester.send('a'), async {
+        let request = responder.recv().await.unwrap();
+        assert!(*request == "AAA");
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    assert!(matches!(response, Ok(6)));
+}
+
+#[test]
+async fn send_fallible() {
+    let (requester, responder) = bounded::<&str, Result<usize, &str>>(1);
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(Ok(len)).unwrap();
+        responder.recv().await.unwrap().respond_err("no").unwrap();
+        drop(responder.recv().await.unwrap());
+    };
+    let requests = async {
+        assert!(matches!(requester.send_fallible("hello").await, Ok(5)));
+        assert!(matches!(
+            requester.send_fallible("hello").await,
+            Err(RequestError::Handler("no"))
+        ));
+        assert!(matches!(
+            requester.send_fallible("hello").await,
+            Err(RequestError::Ignored)
+        ));
+    };
+    join!(handler, requests);
+    for (overflow, shed) in [(Overflow::Reject, false), (Overflow::DropNewest, true)] {
+        let (requester, responder) = bounded_with_policy::<&str, Result<usize, &str>>(1, overflow);
+        let _queued = requester.try_send("hello").unwrap();
+        match requester.send_fallible("world!").await {
+            Err(RequestError::Full("world!")) => assert!(!shed),
+            Err(RequestError::Shed) => assert!(shed),
+            other => panic!("{:?}", other),
+        }
+        drop(responder);
+        assert!(matches!(
+            requester.send_fallible("hello").await,
+            Err(RequestError::Closed("hello"))
+        ));
+    }
+}
+
+#[test]
+async fn request_ids() {
+    let (requester, responder) = bounded_with_ids::<&str, Option<RequestId>>(2);
+    let clone = requester.clone();
+    let handler = async {
+        for _ in 0..2 {
+            let request = responder.recv().await.unwrap();
+            let id = request.id();
+            request.respond(id).unwrap();
+        }
+    };
+    let requests = async { join!(requester.send("a"), clone.send("b")) };
+    let (_, (a, b)) = join!(handler, requests);
+    let (a, b) = (a.unwrap(), b.unwrap());
+    // Echoed back by the responder, and unique across clones
+    assert!(Some(a.0) == a.1 && Some(b.0) == b.1);
+    assert!(a.0 == RequestId(0) && b.0 == RequestId(1));
+}
+
+#[test]
+async fn rendezvous_handover() {
+    let (requester, responder) = rendezvous::<&str, usize>();
+    let mut enqueue = Box::pin(requester.send_and_forget("hello"));
+    assert!(poll!(enqueue.as_mut()).is_pending());
+    // The request is waiting for the handover, so nobody else can get in
+    assert!(matches!(
+        requester.try_send("world"),
+        Err(TrySendError::Full("world"))
+    ));
+    assert!(poll!(enqueue.as_mut()).is_pending());
+    let request = responder.recv().await.unwrap();
+    assert!(*request == "hello");
+    assert!(matches!(poll!(enqueue), std::task::Poll::Ready(Ok(()))));
+}
+
+#[test]
+async fn handles_are_debug() {
+    struct Opaque;
+    let (requester, responder) = bounded::<Opaque, Opaque>(1);
+    assert_eq!(
+        format!("{:?}", requester),
+        "Requester { capacity: Some(1), len: 0, responders_alive: true }"
+    );
+    assert_eq!(
+        format!("{:?}", responder),
+        "Responder { capacity: Some(1), len: 0, requesters_alive: true }"
+    );
+    let (sent, received) = join!(requester.request(Opaque), responder.recv());
+    let (_, unresponded): (Opaque, _) = received.unwrap().into();
+    assert!(format!("{:?}", unresponded).contains("requester_waiting: true"));
+    drop(sent);
+    assert!(format!("{:?}", unresponded).contains("requester_waiting: false"));
+    let received = ReceivedRequest {
+        request: "hello",
+        unresponded,
+    };
+    assert!(format!("{:?}", received).starts_with("ReceivedRequest { request: \"hello\""));
+}
+
+#[test]
+async fn dropped_handle_is_ignored_promptly() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (response, received) = join!(requester.request("hello"), responder.recv());
+    let mut response = response.unwrap();
+    let (request, unresponded) = received.unwrap().into();
+    assert!(poll!(&mut response).is_pending());
+    drop(unresponded);
+    // Still holding on to the request
+    assert!(request == "hello");
+    assert!(matches!(
+        poll!(response),
+        std::task::Poll::Ready(Err(ResponseError::Ignored))
+    ));
+}
+
+#[test]
+#[timeout(1000)]
+async fn deadline_expires() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let deadline = Instant::now() + Duration::from_millis(20);
+    let (response, _) = join!(requester.send_deadline("hello", deadline), async {
+        let request = responder.recv().await.unwrap();
+        assert!(request.deadline() == Some(deadline));
+        assert!(!request.is_expired());
+        async_std::task::sleep(Duration::from_millis(40)).await;
+        assert!(request.is_expired());
+        assert!(request.respond(5).is_err());
+    });
+    assert!(matches!(response, Err(SendRequestError::ResponseTimedOut)));
+}
+
+#[test]
+#[timeout(1000)]
+async fn cancel_while_full() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    requester.send_and_forget("first").await.unwrap();
+    let (cancel, response) = requester.send_cancelable("second");
+    futures::pin_mut!(response);
+    assert!(poll!(&mut response).is_pending());
+    cancel.cancel();
+    assert!(matches!(
+        poll!(&mut response),
+        std::task::Poll::Ready(Err(SendRequestError::Cancelled))
+    ));
+    drop(responder.recv().await.unwrap());
+    // The cancelled request never took the space
+    assert!(requester.is_empty());
+    assert!(matches!(responder.try_recv(), Err(TryRecvError::Empty)));
+}
+
+#[test]
+#[timeout(1000)]
+async fn cancel_in_flight() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (cancel, response) = requester.send_cancelable("hello");
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        assert!(!request.unresponded.is_canceled());
+        cancel.cancel();
+        assert!(request.unresponded.is_canceled());
+        assert!(request.respond(5).is_err());
+    };
+    let (response, _) = join!(response, handler);
+    assert!(matches!(response, Err(SendRequestError::Cancelled)));
+    assert!(cancel.is_canceled());
+}
+
+#[test]
+async fn in_flight_counts_unanswered_requests() {
+    let (requester, responder) = bounded::<_, usize>(2);
+    let (first, second) = join!(requester.request("hello"), requester.request("world"));
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert!(requester.in_flight() == 2);
+    let request = responder.recv().await.unwrap();
+    // Received, but still being worked on
+    assert!(requester.len() == 1);
+    assert!(responder.in_flight() == 2);
+    request.respond(5).unwrap();
+    assert!(requester.in_flight() == 1);
+    drop(responder.recv().await.unwrap());
+    assert!(requester.in_flight() == 0);
+    assert!(first.await == Ok(5));
+    assert!(second.await == Err(ResponseError::Ignored));
+}
+
+#[test]
+async fn handle_counts() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    assert!(requester.sender_count() == 1 && responder.sender_count() == 1);
+    let clone = requester.clone();
+    let weak = requester.downgrade();
+    assert!(requester.sender_count() == 2 && responder.sender_count() == 2);
+    drop(clone);
+    drop(weak);
+    assert!(requester.sender_count() == 1 && responder.sender_count() == 1);
+    let other = responder.clone();
+    assert!(requester.receiver_count() == 2 && responder.receiver_count() == 2);
+    drop(other);
+    assert!(requester.receiver_count() == 1 && responder.receiver_count() == 1);
+}
+
+#[test]
+#[timeout(5000)]
+async fn responders_dropped_together_close_the_channel() {
+    for _ in 0..100 {
+        let (requester, responder) = bounded::<&str, usize>(1);
+        let waiting = requester.request("hello").await.unwrap();
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let threads = [responder.clone(), responder].map(|responder| {
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                drop(responder)
+            })
+        });
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert!(requester.receiver_count() == 0 && requester.is_closed());
+        assert!(waiting.await == Err(ResponseError::Ignored));
+        assert!(matches!(
+            requester.send("hello").await,
+            Err(SendRequestError::Closed(_))
+        ));
+    }
+}
+
+#[test]
+async fn drain_on_shutdown() {
+    let (requester, responder) = bounded::<_, &str>(2);
+    let (first, second) = join!(requester.request("hello"), requester.request("world"));
+    let pending = responder.drain();
+    assert!(matches!(
+        requester.send("late").await,
+        Err(SendRequestError::Closed("late"))
+    ));
+    assert!(pending.len() == 2);
+    for request in pending {
+        request.respond("shutting down").unwrap();
+    }
+    assert!(first.unwrap().await == Ok("shutting down"));
+    assert!(second.unwrap().await == Ok("shutting down"));
+}
+
+#[test]
+async fn peek_then_recv() {
+    let (requester, mut responder) = bounded::<_, usize>(1);
+    assert!(responder.peek().is_none());
+    let response = requester.request("hello").await.unwrap();
+    assert!(responder.peek() == Some(&"hello"));
+    // Peeking again doesn't take another request
+    assert!(responder.peek() == Some(&"hello"));
+    assert!(responder.len() == 1);
+    let request = responder.try_recv().unwrap();
+    assert!(*request == "hello");
+    assert!(matches!(responder.try_recv(), Err(TryRecvError::Empty)));
+    request.respond(5).unwrap();
+    assert!(response.await == Ok(5));
+    drop(requester);
+    assert!(matches!(responder.try_recv(), Err(TryRecvError::Closed)));
+}
+
+#[test]
+#[timeout(1000)]
+async fn dropping_recv_keeps_the_peeked_request() {
+    let (requester, mut responder) = bounded::<_, usize>(1);
+    let response = requester.request("hello").await.unwrap();
+    assert!(responder.peek() == Some(&"hello"));
+    drop(responder.recv());
+    // Another branch of a select wins before the receive is polled
+    let raced = futures::future::select(futures::future::ready(()), responder.recv()).await;
+    assert!(matches!(raced, futures::future::Either::Left(_)));
+    let request = responder.recv().await.unwrap();
+    assert!(*request == "hello");
+    request.respond(5).unwrap();
+    assert!(response.await == Ok(5));
+}
+
+#[test]
+#[timeout(1000)]
+async fn select_any_finds_the_busy_responder() {
+    let (first, first_responder) = bounded::<&str, usize>(1);
+    let (second, second_responder) = bounded::<&str, usize>(1);
+    let mut responders = vec![first_responder, second_responder];
+    for _ in 0..10 {
+        let (response, received) = join!(second.request("hello"), select_any(&mut responders));
+        let (index, request) = received.unwrap();
+        assert!(index == 1);
+        request.respond(5).unwrap();
+        assert!(response.unwrap().await == Ok(5));
+    }
+    drop((first, second));
+    assert!(select_any(&mut responders).await.is_err());
+}
+
+#[test]
+async fn confirmation_before_response() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (enqueued, mut response) = requester.send_and_confirm("hello");
+    enqueued.await.unwrap();
+    assert!(requester.len() == 1);
+    assert!(poll!(&mut response).is_pending());
+    responder.recv().await.unwrap().respond(5).unwrap();
+    assert!(response.await == Ok(5));
+
+    let (enqueued, response) = requester.send_and_confirm("ignored");
+    enqueued.await.unwrap();
+    drop(responder.recv().await.unwrap());
+    assert!(response.await == Err(ResponseError::Ignored));
+
+    drop(responder);
+    let (enqueued, _) = requester.send_and_confirm("closed");
+    assert!(matches!(
+        enqueued.await,
+        Err(EnqueueError::Closed("closed"))
+    ));
+}
+
+#[test]
+async fn respond_then_continue() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (response, received) = join!(requester.request(String::from("hello")), responder.recv());
+    let mut response = response.unwrap();
+    let (request, unresponded) = received.unwrap().split();
+    unresponded.respond(request.len()).unwrap();
+    // The requester has its response while the request is still held
+    assert!(poll!(&mut response) == std::task::Poll::Ready(Ok(5)));
+    assert!(request == "hello");
+}
+
+#[test]
+#[timeout(1000)]
+async fn resize_at_runtime() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let first = requester.request("first").await.unwrap();
+    assert!(requester.is_full());
+    let mut second = Box::pin(requester.request("second"));
+    assert!(poll!(second.as_mut()).is_pending());
+    // Growing lets the waiting request on straight away
+    requester.set_capacity(3);
+    assert!(requester.capacity() == Some(3));
+    let second = second.await.unwrap();
+    let third = requester.request("third").await.unwrap();
+    assert!(requester.len() == 3);
+
+    // Shrinking keeps everything already on the channel
+    responder.set_capacity(2);
+    assert!(requester.is_full());
+    assert!(matches!(
+        requester.try_send("rejected"),
+        Err(TrySendError::Full("rejected"))
+    ));
+    for (response, expected) in [(first, "first"), (second, "second")] {
+        let request = responder.recv().await.unwrap();
+        assert!(*request == expected);
+        request.respond(expected.len()).unwrap();
+        assert!(response.await == Ok(expected.len()));
+    }
+    // Only one left, so there's space again
+    assert!(!requester.is_full());
+    let _fourth = requester.try_send("fourth").unwrap();
+    assert!(requester.is_full());
+    drop(third);
+}
+
+#[test]
+#[timeout(1000)]
+async fn cloned_responders_share_the_work() {
+    let (requester, responder) = bounded::<usize, (usize, usize)>(5);
+    let worker = |id: usize, responder: Responder<usize, (usize, usize)>| async move {
+        let mut handled = 0;
+        while let Ok(request) = responder.recv().await {
+            let n = *request;
+            request.respond((id, n * 2)).unwrap();
+            handled += 1;
+            // Let the other worker have a go
+            async_std::task::yield_now().await;
+        }
+        handled
+    };
+    let workers = async { join!(worker(0, responder.clone()), worker(1, responder)) };
+    let requests = async move {
+        let responses = join_all((0..5).map(|n| requester.send(n))).await;
+        responses
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+    };
+    let (responses, (first, second)) = join!(requests, workers);
+    assert!(responses
+        .iter()
+        .map(|(_, doubled)| *doubled)
+        .eq((0..5).map(|n| n * 2)));
+    assert!(first + second == 5);
+    assert!(first > 0 && second > 0, "{} {}", first, second);
+}
+
+#[test]
+async fn metadata_travels_with_the_request() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Auth(&'static str);
+    let (requester, responder) = bounded_with_meta::<_, usize, Auth>(1);
+    let (response, _) = join!(requester.send_with("hello", Auth("secret")), async {
+        let mut request = responder.recv().await.unwrap();
+        assert!(request.meta() == &Auth("secret"));
+        request.respond_ref(5).unwrap();
+        // Still readable after responding
+        assert!(request.meta() == &Auth("secret"));
+    });
+    assert!(response.unwrap() == 5);
+    // Without metadata, the default is attached
+    let (_, request) = join!(requester.request("plain"), responder.recv());
+    let (_, unresponded) = request.unwrap().split();
+    assert!(unresponded.meta() == &Auth::default());
+}
+
+#[test]
+#[timeout(1000)]
+async fn responder_sees_requester_give_up() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let (response, received) = join!(requester.request("hello"), responder.recv());
+    let mut unresponded = received.unwrap().unresponded;
+    assert!(unresponded.is_requester_waiting());
+    let mut cancelled = Box::pin(unresponded.wait_for_cancellation());
+    assert!(poll!(cancelled.as_mut()).is_pending());
+    drop(response);
+    cancelled.await;
+    assert!(!unresponded.is_requester_waiting());
+    assert!(unresponded.respond(5).is_err());
+}
+
+#[test]
+async fn notifications_need_no_response() {
+    let (requester, responder) = bounded::<_, ()>(2);
+    requester.notify("started").await.unwrap();
+    requester.send_and_forget("request").await.unwrap();
+    assert!(requester.in_flight() == 1);
+    let notification = responder.recv().await.unwrap();
+    assert!(notification.is_notification());
+    assert!(notification.respond(()).is_ok());
+    let request = responder.recv().await.unwrap();
+    assert!(!request.is_notification());
+    drop(request);
+    drop(responder);
+    assert!(matches!(
+        requester.notify("stopped").await,
+        Err(EnqueueError::Closed("stopped"))
+    ));
+}
+
+#[test]
+async fn request_macro_returns_early() {
+    async fn ask(requester: &Requester<&'static str, usize>) -> anyhow::Result<usize> {
+        let len = bidirectional_channel::request!(requester, "hello");
+        Ok(len + 1)
+    }
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let responder = async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap();
+        responder
+    };
+    let (response, responder) = join!(ask(&requester), responder);
+    assert!(response.unwrap() == 6);
+    drop(responder);
+    let error = ask(&requester).await.unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<SendRequestError<&str>>(),
+        Some(SendRequestError::Closed("hello"))
+    ));
+}
+
+#[test]
+async fn send_errors_convert_to_io_errors() {
+    use std::io::{Error, ErrorKind};
+    let kind = |e: SendRequestError<&str>| Error::from(e).kind();
+    assert!(kind(SendRequestError::Closed("hello")) == ErrorKind::BrokenPipe);
+    assert!(kind(SendRequestError::Ignored) == ErrorKind::ConnectionReset);
+    assert!(kind(SendRequestError::TimedOut("hello")) == ErrorKind::TimedOut);
+    assert!(kind(SendRequestError::ResponseTimedOut) == ErrorKind::TimedOut);
+    assert!(kind(SendRequestError::Cancelled) == ErrorKind::Other);
+
+    async fn ask(requester: &Requester<&'static str, usize>) -> std::io::Result<usize> {
+        Ok(requester.send("hello").await?)
+    }
+    let (requester, responder) = bounded(1);
+    drop(responder);
+    let error = ask(&requester).await.unwrap_err();
+    assert!(error.kind() == ErrorKind::BrokenPipe);
+    assert!(error.to_string() == "The Responder was dropped before the message was sent");
+}
+
+#[test]
+async fn send_ref_shares_the_request() {
+    let (requester, responder) = bounded::<Arc<Vec<u8>>, usize>(1);
+    let buffer = Arc::new(vec![0; 1024]);
+    let responder = async {
+        let request = responder.recv().await.unwrap();
+        assert!(Arc::ptr_eq(&request, &buffer));
+        assert!(Arc::strong_count(&buffer) == 2);
+        let len = request.len();
+        request.respond(len).unwrap()
+    };
+    let (response, _) = join!(requester.send_ref(&buffer), responder);
+    assert!(response.unwrap() == 1024);
+    assert!(Arc::strong_count(&buffer) == 1);
+}
+
+#[test]
+async fn recv_many_takes_what_is_waiting() {
+    let (requester, responder) = bounded::<usize, usize>(4);
+    let responses = join_all((0..3).map(|i| requester.send(i)));
+    let responder = async {
+        let batch = responder.recv_many(2).await;
+        assert!(batch.len() == 2);
+        let rest = responder.recv_many(8).await;
+        assert!(rest.len() == 1);
+        for request in batch.into_iter().chain(rest) {
+            let doubled = *request * 2;
+            request.respond(doubled).unwrap();
+        }
+        responder
+    };
+    let (responses, responder) = join!(responses, responder);
+    let responses: Vec<_> = responses.into_iter().map(Result::unwrap).collect();
+    assert!(responses == [0, 2, 4]);
+    drop(requester);
+    assert!(responder.recv_many(2).await.is_empty());
+}
+
+#[test]
+async fn refused_requests_are_measured_as_full() {
+    use std::sync::Mutex;
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::Reject);
+    let requester = requester.with_metrics({
+        let reported = reported.clone();
+        move |metrics: RequestMetrics| reported.lock().unwrap().push(metrics)
+    });
+    let _occupying = requester.try_send("occupying").unwrap();
+    assert!(matches!(
+        requester.try_send("a"),
+        Err(TrySendError::Full("a"))
+    ));
+    assert!(matches!(
+        requester.send("b").await,
+        Err(SendRequestError::Full("b"))
+    ));
+    let outcomes: Vec<_> = reported.lock().unwrap().iter().map(|m| m.outcome).collect();
+    assert!(outcomes == [Outcome::Full, Outcome::Full]);
+    drop(responder);
+}
+
+#[test]
+async fn pending_poll_send_is_one_request() {
+    use futures::future::poll_fn;
+    use std::sync::Mutex;
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let mut requester = requester.with_metrics({
+        let reported = reported.clone();
+        move |metrics: RequestMetrics| reported.lock().unwrap().push(metrics)
+    });
+    let _occupying = requester.try_send("occupying").unwrap();
+    let mut request = Some("hello");
+    for _ in 0..3 {
+        assert!(poll!(poll_fn(|cx| requester.poll_send(cx, &mut request))).is_pending());
+    }
+    assert!(requester.in_flight() == 2);
+    assert!(reported.lock().unwrap().is_empty());
+    assert!(requester.abandon_send() == Some("hello"));
+    assert!(requester.in_flight() == 1);
+    let outcomes: Vec<_> = reported.lock().unwrap().iter().map(|m| m.outcome).collect();
+    assert!(outcomes == [Outcome::Full]);
+    drop(responder);
+}
+
+#[test]
+async fn poll_based_send_and_recv() {
+    use futures::future::poll_fn;
+    use std::{future::Future, pin::Pin};
+    let (mut requester, mut responder) = bounded::<&str, usize>(1);
+    requester.send_and_forget("occupying").await.unwrap();
+    let mut request = Some("hello");
+    assert!(poll!(poll_fn(|cx| requester.poll_send(cx, &mut request))).is_pending());
+    // Held by the requester until there's space
+    assert!(request.is_none());
+    drop(responder.try_recv().unwrap());
+
+    let mut response = poll_fn(|cx| requester.poll_send(cx, &mut request))
+        .await
+        .unwrap();
+    assert!(request.is_none());
+    let received = poll_fn(|cx| responder.poll_recv(cx)).await.unwrap();
+    let len = received.len();
+    received.respond(len).unwrap();
+    let response = poll_fn(|cx| Pin::new(&mut response).poll(cx)).await;
+    assert!(response == Ok(5));
+}
+
+#[test]
+async fn send_overwrite_sheds_the_oldest() {
+    let (requester, responder) = bounded::<usize, usize>(2);
+    let oldest = requester.send_overwrite(0).unwrap();
+    let kept = requester.send_overwrite(1).unwrap();
+    let newest = requester.send_overwrite(2).unwrap();
+    assert!(oldest.await == Err(ResponseError::Shed));
+    assert!(responder.len() == 2);
+    for _ in 0..2 {
+        let request = responder.recv().await.unwrap();
+        let doubled = *request * 2;
+        request.respond(doubled).unwrap();
+    }
+    assert!(kept.await == Ok(2));
+    assert!(newest.await == Ok(4));
+    assert!(responder.receiver_count() == 1 && requester.receiver_count() == 1);
+    drop(responder);
+    assert!(matches!(
+        requester.send_overwrite(3),
+        Err(EnqueueError::Closed(3))
+    ));
+}
+
+#[test]
+async fn metrics_report_each_outcome() {
+    use std::sync::Mutex;
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let requester = requester.with_metrics({
+        let reported = reported.clone();
+        move |metrics: RequestMetrics| reported.lock().unwrap().push(metrics)
+    });
+    let responding = async {
+        let request = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(10)).await;
+        request.respond(5).unwrap();
+        drop(responder.recv().await.unwrap());
+        responder
+    };
+    let (responded, ignored, responder) = join!(
+        requester.send("responded"),
+        requester.send("ignored"),
+        responding
+    );
+    assert!(responded.unwrap() == 5 && matches!(ignored, Err(SendRequestError::Ignored)));
+    drop(responder);
+    assert!(requester.send("closed").await.is_err());
+
+    let reported = reported.lock().unwrap();
+    let outcomes: Vec<_> = reported.iter().map(|metrics| metrics.outcome).collect();
+    assert!(outcomes == [Outcome::Responded, Outcome::Ignored, Outcome::Closed]);
+    assert!(reported[0].handler_time.unwrap() >= Duration::from_millis(10));
+    assert!(reported[0].round_trip >= reported[0].handler_time.unwrap());
+    assert!(reported[1].queue_wait.is_some() && reported[1].handler_time.is_none());
+    assert!(reported[2].queue_wait.is_none());
+}
+
+#[test]
+async fn send_errors_give_the_request_back() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    drop(responder);
+    let error = requester.send("hello").await.unwrap_err();
+    assert!(error.is_closed() && !error.is_ignored());
+    assert!(error.into_inner() == Some("hello"));
+    let ignored = SendRequestError::<&str>::Ignored;
+    assert!(ignored.is_ignored() && !ignored.is_closed());
+    assert!(ignored.into_inner().is_none());
+}
+
+#[test]
+async fn send_errors_compare_equal() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let handler = async move { drop(responder.recv().await.unwrap()) };
+    let (ignored, ()) = join!(requester.send("hello"), handler);
+    assert_eq!(ignored, Err(SendRequestError::Ignored));
+    let closed = requester.send("hello").await;
+    assert_eq!(closed, Err(SendRequestError::Closed("hello")));
+    assert_eq!(closed.clone(), closed);
+    assert_ne!(closed, Err(SendRequestError::Closed("goodbye")));
+}
+
+#[test]
+async fn responder_stream_stays_ended() {
+    use futures::{select, stream::FusedStream};
+    let (requester, mut responder) = bounded::<&str, usize>(1);
+    drop(requester);
+    assert!(responder.next().await.is_none());
+    assert!(responder.next().await.is_none());
+    assert!(responder.is_terminated());
+    let selected = select! {
+        request = responder.next() => request.is_some(),
+        complete => false,
+    };
+    assert!(!selected);
+}
+
+#[test]
+async fn try_respond_keeps_the_handle() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let deadline = Instant::now() + Duration::from_millis(50);
+    let (response, received) = join!(requester.send_deadline("hello", deadline), async {
+        let mut received = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(100)).await;
+        // Too late, but the handle can still be read
+        assert!(received.unresponded.try_respond(5) == Err(5));
+        assert!(received.unresponded.deadline() == Some(deadline));
+        received
+    });
+    assert!(response.is_err());
+    drop(received);
+
+    let (response, ()) = join!(requester.send("hello"), async {
+        let (_, mut unresponded) = responder.recv().await.unwrap().split();
+        assert!(unresponded.try_respond(5) == Ok(()));
+        assert!(requester.in_flight() == 0);
+        // Spent
+        assert!(unresponded.try_respond(6) == Err(6));
+    });
+    assert!(response.unwrap() == 5);
+}
+
+#[test]
+async fn responses_collects_forgotten_sends() {
+    let (requester, responder) = bounded::<usize, usize>(4);
+    let responses = requester.responses();
+    let forward = futures::stream::iter(0..4)
+        .map(Ok)
+        .forward(requester.clone().into_sink());
+    forward.await.unwrap();
+    drop(requester);
+    // Respond out of order, and ignore one
+    let mut received: Vec<_> = responder.drain();
+    let ignored = received.remove(1);
+    drop(ignored);
+    for request in received.into_iter().rev() {
+        let doubled = *request * 2;
+        request.respond(doubled).unwrap();
+    }
+    assert!(responses.collect::<Vec<_>>().await == [0, 4, 6]);
+}
+
+#[test]
+async fn channel_wide_timeout() {
+    let (requester, responder) = bounded_with_timeout::<&str, usize>(1, Duration::from_millis(20));
+    assert!(requester.timeout() == Duration::from_millis(20));
+    let (response, received) = join!(requester.send("hello"), responder.recv());
+    assert!(matches!(response, Err(SendRequestError::ResponseTimedOut)));
+    drop(received);
+
+    let slow = async {
+        let request = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(50)).await;
+        let len = request.len();
+        request.respond(len).unwrap()
+    };
+    let (response, _) = join!(requester.send_no_timeout("hello"), slow);
+    assert!(response.unwrap() == 5);
+    let slow = async {
+        let request = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(50)).await;
+        let len = request.len();
+        request.respond(len).unwrap()
+    };
+    let (response, _) = join!(
+        requester.send_timeout("hello", Duration::from_secs(5)),
+        slow
+    );
+    assert!(response.unwrap() == 5);
+}
+
+#[test]
+async fn ping_skips_the_handler() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let response = requester.request("hello").await.unwrap();
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        assert!(*request == "hello");
+        request.respond(5).unwrap();
+        assert!(responder.try_recv().is_err());
+    };
+    let (ping, _) = join!(requester.ping(), handler);
+    ping.unwrap();
+    assert!(response.await == Ok(5));
+    assert!(requester.in_flight() == 0);
+
+    drop(responder);
+    assert!(matches!(
+        requester.ping().await,
+        Err(SendRequestError::Closed(()))
+    ));
+}
+
+#[test]
+async fn failed_respond_returns_the_request() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let response = requester.request("hello").await.unwrap();
+    let received = responder.recv().await.unwrap();
+    drop(response);
+    let (received, response) = received.try_respond(5).unwrap_err();
+    assert!(*received == "hello" && response == 5);
+
+    let response = requester.request("world").await.unwrap();
+    let (stale, fresh) = (received, responder.recv().await.unwrap());
+    drop(stale);
+    assert!(matches!(fresh.try_respond(5), Ok("world")));
+    assert!(response.await == Ok(5));
+}
+
+#[test]
+async fn serve_fn_answers_until_closed() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let requests = async move {
+        assert!(requester.send("hello").await.unwrap() == 5);
+        drop(requester.request("ignored").await.unwrap());
+        assert!(requester.send("hi").await.unwrap() == 2);
+    };
+    join!(
+        requests,
+        responder.serve_fn(|request| async move { request.len() })
+    );
+
+    let (requester, responder) = bounded::<u64, u64>(4);
+    let requests = async move {
+        let (slow, fast) = join!(requester.send(50), requester.send(0));
+        assert!(slow.unwrap() == 50 && fast.unwrap() == 0);
+    };
+    join!(
+        requests,
+        responder.serve_fn_concurrent(2, |millis| async move {
+            async_std::task::sleep(Duration::from_millis(millis)).await;
+            millis
+        })
+    );
+}
+
+#[test]
+async fn map_request_keeps_the_handle() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let mut uppercase = responder.map_request(str::to_uppercase);
+    let response = requester.request("hello").await.unwrap();
+    let request = uppercase.next().await.unwrap();
+    assert!(*request == "HELLO");
+    let len = request.len();
+    request.respond(len).unwrap();
+    assert!(response.await == Ok(5));
+
+    drop(requester);
+    assert!(uppercase.next().await.is_none());
+}
+
+#[test]
+async fn dropping_any_handle_is_ignored_promptly() {
+    type Discard = fn(ReceivedRequest<&'static str, usize>);
+    let drops: [Discard; 3] = [
+        |received| drop(received),
+        |received| drop(received.split().1),
+        |received| {
+            let (_request, unresponded): (_, UnRespondedRequest<usize>) = received.into();
+            drop(unresponded)
+        },
+    ];
+    for drop_handle in drops {
+        let (requester, responder) = bounded::<&str, usize>(1);
+        let handler = async {
+            let received = responder.recv().await.unwrap();
+            // Make sure the requester is already waiting
+            async_std::task::sleep(Duration::from_millis(10)).await;
+            drop_handle(received);
+            Instant::now()
+        };
+        let (result, dropped) = join!(
+            async {
+                let result = requester.send("hello").await;
+                (result, Instant::now())
+            },
+            handler
+        );
+        let (result, resolved) = result;
+        assert!(matches!(result, Err(SendRequestError::Ignored)));
+        assert!(resolved.duration_since(dropped) < Duration::from_millis(5));
+    }
+}
+
+#[test]
+async fn named_channels_show_their_name() {
+    let (requester, responder) = bounded_named::<&str, usize>(1, "db");
+    assert!(requester.name() == Some("db") && responder.name() == Some("db"));
+    assert!(format!("{:?}", requester).contains("\"db\""));
+    assert!(format!("{:?}", responder).contains("\"db\""));
+    assert!(bounded::<&str, usize>(1).0.name().is_none());
+
+    let (sender, reported) = std::sync::mpsc::channel();
+    let sender = std::sync::Mutex::new(sender);
+    let requester = requester
+        .with_metrics(move |metrics: RequestMetrics| sender.lock().unwrap().send(metrics).unwrap());
+    drop(responder);
+    assert!(requester.send("closed").await.is_err());
+    assert!(reported.recv().unwrap().channel.as_deref() == Some("db"));
+}
+
+#[test]
+async fn respond_from_another_task() {
+    fn assert_send_static<T: Send + 'static>() {}
+    assert_send_static::<UnRespondedRequest<String>>();
+    assert_send_static::<ReceivedRequest<String, String>>();
+
+    let (requester, responder) = bounded::<String, usize>(1);
+    let response = requester.request(String::from("hello")).await.unwrap();
+    let (request, unresponded) = responder.recv().await.unwrap().split();
+    async_std::task::spawn(async move { unresponded.respond(request.len()).unwrap() }).await;
+    assert!(response.await == Ok(5));
+}
+
+#[test]
+async fn send_all_keeps_the_order() {
+    let (requester, responder) = bounded::<&str, usize>(2);
+    let requests = async move {
+        let responses = requester.send_all(["hello"; 5]).await;
+        assert!(responses == [Ok(5), Ok(5), Ok(5), Ok(5), Ok(5)]);
+        assert!(requester.send_all_ok(vec!["a", "bb", "ccc"]).await == Ok(vec![1, 2, 3]));
+        let responses = requester.send_all(vec!["fine", "ignored"]).await;
+        assert!(responses == [Ok(4), Err(SendRequestError::Ignored)]);
+        assert!(
+            requester.send_all_ok(vec!["ignored", "fine"]).await == Err(SendRequestError::Ignored)
+        );
+    };
+    let handler = async move {
+        while let Ok(request) = responder.recv().await {
+            if *request != "ignored" {
+                let len = request.len();
+                let _ = request.respond(len);
+            }
+        }
+    };
+    join!(requests, handler);
+}
+
+#[test]
+async fn close_and_wait_waits_for_every_request() {
+    let (requester, responder) = bounded::<usize, usize>(5);
+    for i in 0..5 {
+        requester.send_and_forget(i).await.unwrap();
+    }
+    let handled = std::cell::Cell::new(0);
+    let handler = async {
+        while let Ok(request) = responder.recv().await {
+            async_std::task::sleep(Duration::from_millis(5)).await;
+            handled.set(handled.get() + 1);
+            let _ = request.respond(0);
+        }
+    };
+    let (handled_by_then, ()) = join!(
+        async {
+            requester.close_and_wait().await;
+            handled.get()
+        },
+        handler
+    );
+    assert!(handled_by_then == 5);
+
+    let (requester, responder) = bounded::<usize, usize>(2);
+    let response = requester.request(0).await.unwrap();
+    drop(responder);
+    requester.close_and_wait().await;
+    assert!(response.await == Err(ResponseError::Ignored));
+}
+
+#[test]
+async fn timeout_composes_with_mapping() {
+    let (requester, responder) = bounded::<String, usize>(1);
+    let requester = requester
+        .timeout(Duration::from_millis(20))
+        .map_request(|request: &str| request.to_uppercase());
+    let (response, received) = join!(requester.send("hello"), responder.recv());
+    assert!(*received.unwrap() == "HELLO");
+    assert!(matches!(response, Err(SendRequestError::ResponseTimedOut)));
+
+    let requester = requester.into_inner().map_response(|len| len * 2);
+    let requester = requester.timeout(Duration::from_secs(5));
+    let handler = async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap()
+    };
+    let (response, _) = join!(requester.send(String::from("hi")), handler);
+    assert!(response.unwrap() == 4);
+}
+
+#[test]
+async fn closed_resolves_once_the_requesters_are_gone() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let weak = requester.downgrade();
+    let clone = requester.clone();
+    let mut closed = Box::pin(responder.closed());
+    assert!(poll!(closed.as_mut()).is_pending());
+    drop(requester);
+    assert!(poll!(closed.as_mut()).is_pending());
+    let dropped = async {
+        async_std::task::sleep(Duration::from_millis(10)).await;
+        drop(clone)
+    };
+    join!(closed, dropped);
+    drop(weak);
+
+    let (requester, responder) = bounded::<&str, usize>(1);
+    join!(responder.closed(), async { requester.close() });
+}
+
+#[test]
+async fn overflow_policies() {
+    // Block waits for space
+    let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::Block);
+    let first = requester.request("first").await.unwrap();
+    let mut second = Box::pin(requester.request("second"));
+    assert!(poll!(second.as_mut()).is_pending());
+    drop(responder.recv().await.unwrap());
+    assert!(first.await == Err(ResponseError::Ignored));
+    let _second = second.await.unwrap();
+
+    // Reject gives the new request back
+    let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::Reject);
+    let first = requester.request("first").await.unwrap();
+    assert!(matches!(
+        requester.request("second").await,
+        Err(EnqueueError::Full("second"))
+    ));
+    assert!(requester.send("second").await == Err(SendRequestError::Full("second")));
+    assert!(matches!(
+        requester.try_send("second"),
+        Err(TrySendError::Full("second"))
+    ));
+    assert!(*responder.recv().await.unwrap() == "first");
+    drop(first);
+
+    // DropOldest sheds what's on the channel
+    let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::DropOldest);
+    let first = requester.request("first").await.unwrap();
+    let second = requester.request("second").await.unwrap();
+    assert!(first.await == Err(ResponseError::Shed));
+    let received = responder.recv().await.unwrap();
+    assert!(*received == "second");
+    received.respond(6).unwrap();
+    assert!(second.await == Ok(6));
+
+    // DropNewest sheds the new request
+    let (requester, responder) = bounded_with_policy::<&str, usize>(1, Overflow::DropNewest);
+    let first = requester.request("first").await.unwrap();
+    assert!(requester.send("second").await == Err(SendRequestError::Shed));
+    let received = responder.recv().await.unwrap();
+    assert!(*received == "first");
+    received.respond(5).unwrap();
+    assert!(first.await == Ok(5));
+    assert!(responder.try_recv().is_err());
+}
+
+#[test]
+async fn queue_depth_counts_the_requests_behind() {
+    let (requester, responder) = bounded::<usize, ()>(3);
+    let mut responses = Vec::new();
+    for i in 0..3 {
+        responses.push(requester.request(i).await.unwrap());
+    }
+    for behind in (0..3).rev() {
+        let request = responder.recv().await.unwrap();
+        assert!(request.queue_depth() == behind);
+        let (_, unresponded) = request.split();
+        assert!(unresponded.queue_depth() == behind);
+    }
+}
+
+#[test]
+#[timeout(10000)]
+async fn requests_racing_the_last_responder_are_dropped() {
+    for _ in 0..1000 {
+        let (requester, responder) = unbounded::<(), ()>();
+        let dropping = std::thread::spawn(move || drop(responder));
+        // Either refused, or dropped along with the queue, but never left waiting
+        if let Ok(response) = requester.request(()).await {
+            assert!(response.await == Err(ResponseError::Ignored));
+        }
+        dropping.join().unwrap();
+    }
+}
+
+#[test]
+#[timeout(10000)]
+async fn requests_racing_close_are_received() {
+    for _ in 0..1000 {
+        let (requester, mut responder) = unbounded::<(), ()>();
+        let closing = responder.clone();
+        let sending = async_std::task::spawn(async move { requester.try_send(()).is_ok() });
+        closing.close();
+        let mut received = 0;
+        while responder.next().await.is_some() {
+            received += 1;
+        }
+        assert!(received == usize::from(sending.await));
+        assert!(responder.is_terminated());
+    }
+}
@@ -0,0 +1,102 @@
+use async_std::test;
+use bidirectional_channel::{batching::BatchingRequester, bounded};
+use futures::{future::poll_fn, poll};
+use std::{
+    sync::{Mutex, Once},
+    thread::{self, ThreadId},
+    time::Duration,
+};
+
+/// Collects the warnings logged by each test's thread
+struct Warnings(Mutex<Vec<(ThreadId, String)>>);
+
+impl log::Log for Warnings {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((thread::current().id(), record.args().to_string()))
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static WARNINGS: Warnings = Warnings(Mutex::new(Vec::new()));
+
+/// The warnings logged on this thread so far
+fn warnings() -> Vec<String> {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        log::set_logger(&WARNINGS).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+    let this = thread::current().id();
+    WARNINGS
+        .0
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(thread, _)| *thread == this)
+        .map(|(_, warning)| warning.clone())
+        .collect()
+}
+
+#[test]
+async fn forgotten_response_is_warned_about() {
+    assert!(warnings().is_empty());
+    let (requester, responder) = bounded::<(), ()>(1);
+    let response = requester.request(()).await.unwrap();
+    drop(responder.recv().await.unwrap());
+    assert!(warnings().len() == 1);
+    drop(response);
+}
+
+#[test]
+async fn refused_requests_are_not_warned_about() {
+    assert!(warnings().is_empty());
+    let (requester, _responder) = bounded::<(), ()>(1);
+    let _queued = requester.try_send(()).unwrap();
+    assert!(requester.try_send(()).is_err());
+    // Given up on while the channel is full
+    let mut blocked = Box::pin(requester.send(()));
+    assert!(poll!(blocked.as_mut()).is_pending());
+    drop(blocked);
+    let (requester, responder) = bounded::<(), ()>(1);
+    drop(responder);
+    assert!(requester.try_send(()).is_err());
+    assert!(requester.request(()).await.is_err());
+    assert!(warnings().is_empty(), "{:?}", warnings());
+}
+
+#[test]
+async fn held_poll_send_is_not_warned_about() {
+    assert!(warnings().is_empty());
+    let (mut requester, _responder) = bounded::<(), ()>(1);
+    let _queued = requester.try_send(()).unwrap();
+    let mut request = Some(());
+    assert!(poll!(poll_fn(|cx| requester.poll_send(cx, &mut request))).is_pending());
+    drop(requester);
+    assert!(warnings().is_empty(), "{:?}", warnings());
+}
+
+#[test]
+async fn batches_flushed_while_full_are_not_warned_about() {
+    assert!(warnings().is_empty());
+    let (requester, _responder) = bounded::<Vec<()>, Vec<()>>(1);
+    let _queued = requester.try_send(vec![]).unwrap();
+    let batching = BatchingRequester::new(requester, 1, Duration::from_secs(60));
+    let mut send = Box::pin(batching.send(()));
+    for _ in 0..3 {
+        assert!(poll!(send.as_mut()).is_pending());
+    }
+    drop(send);
+    // Flushing on drop is refused too
+    drop(batching);
+    assert!(warnings().is_empty(), "{:?}", warnings());
+}
@@ -0,0 +1,69 @@
+use async_std::test;
+use bidirectional_channel::{
+    bounded,
+    pool::{RequesterPool, Strategy},
+    Responder, SendRequestError,
+};
+use futures::{future::join_all, join};
+
+/// Respond to every request with this worker's id
+async fn worker(id: usize, responder: Responder<(), usize>) {
+    while let Ok(request) = responder.recv().await {
+        let _ = request.respond(id);
+    }
+}
+
+#[test]
+async fn round_robin() {
+    let (requesters, responders): (Vec<_>, Vec<_>) = (0..3).map(|_| bounded(1)).unzip();
+    let pool = RequesterPool::new(requesters, Strategy::RoundRobin);
+    let workers = join_all(
+        responders
+            .into_iter()
+            .enumerate()
+            .map(|(id, r)| worker(id, r)),
+    );
+    let requests = async {
+        let mut responses = Vec::new();
+        for _ in 0..6 {
+            responses.push(pool.send(()).await.unwrap());
+        }
+        drop(pool);
+        responses
+    };
+    let (responses, _) = join!(requests, workers);
+    assert!(responses == [0, 1, 2, 0, 1, 2])
+}
+
+#[test]
+async fn least_loaded() {
+    let (busy, busy_responder) = bounded::<(), usize>(2);
+    let (idle, idle_responder) = bounded::<(), usize>(2);
+    busy.send_and_forget(()).await.unwrap();
+    let pool = RequesterPool::new(vec![busy, idle], Strategy::LeastLoaded);
+    let (response, _) = join!(pool.send(()), async {
+        let request = idle_responder.recv().await.unwrap();
+        request.respond(1).unwrap()
+    });
+    assert!(response.unwrap() == 1);
+    drop(busy_responder)
+}
+
+#[test]
+async fn retry_closed() {
+    let (closed, closed_responder) = bounded::<(), usize>(1);
+    let (open, open_responder) = bounded::<(), usize>(1);
+    drop(closed_responder);
+    let pool = RequesterPool::new(vec![closed.clone(), open.clone()], Strategy::RoundRobin);
+    assert!(matches!(
+        pool.send(()).await,
+        Err(SendRequestError::Closed(()))
+    ));
+    // Starts with the closed backend again, but moves on to the open one
+    let pool = RequesterPool::new(vec![closed, open], Strategy::RoundRobin).retry_closed(true);
+    let (response, _) = join!(pool.send(()), async {
+        let request = open_responder.recv().await.unwrap();
+        request.respond(1).unwrap()
+    });
+    assert!(response.unwrap() == 1)
+}
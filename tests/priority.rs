@@ -0,0 +1,115 @@
+use async_std::test;
+use bidirectional_channel::{
+    priority::{bounded, unbounded, unbounded_priority, PriorityConfig},
+    SendRequestError,
+};
+use futures::{future::join_all, join};
+use ntest::timeout;
+use std::time::{Duration, Instant};
+
+#[test]
+#[timeout(1000)]
+async fn highest_first() {
+    let (requester, responder) = unbounded::<usize, usize>(PriorityConfig::default());
+    let requests = join_all(
+        [(1, 1.0), (2, 5.0), (3, 1.0), (4, 3.0)]
+            .iter()
+            .map(|(request, priority)| requester.send(*request, *priority)),
+    );
+    let handler = async {
+        // Let every request reach the channel
+        while requester.len() < 4 {
+            async_std::task::yield_now().await
+        }
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            let request = responder.recv().await.unwrap();
+            order.push(*request);
+            request.respond(0).unwrap();
+        }
+        order
+    };
+    let (_, order) = join!(requests, handler);
+    // Ties are FIFO
+    assert!(order == [2, 4, 1, 3], "{:?}", order);
+}
+
+#[test]
+#[timeout(3000)]
+async fn aging_prevents_starvation() {
+    let (requester, responder) = bounded::<&str, ()>(8, PriorityConfig { aging_rate: 100.0 });
+    // Keep the channel full of high priority requests
+    for _ in 0..4 {
+        let requester = requester.clone();
+        async_std::task::spawn(async move { while requester.send("high", 10.0).await.is_ok() {} });
+    }
+    let low = async_std::task::spawn({
+        let requester = requester.clone();
+        async move { requester.send("low", 0.0).await }
+    });
+    let start = Instant::now();
+    loop {
+        let request = responder.recv().await.unwrap();
+        async_std::task::sleep(Duration::from_millis(1)).await;
+        let request = request.respond(()).unwrap();
+        if request == "low" {
+            break;
+        }
+    }
+    // Overtakes after 100ms of waiting, give or take scheduling
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(low.await.is_ok());
+}
+
+#[test]
+async fn closed() {
+    let (requester, responder) = bounded::<&str, ()>(1, PriorityConfig::default());
+    drop(responder);
+    assert!(matches!(
+        requester.send("hello", 0.0).await,
+        Err(SendRequestError::Closed("hello"))
+    ));
+}
+
+#[test]
+async fn exhausted() {
+    let (requester, responder) = bounded::<&str, ()>(1, PriorityConfig::default());
+    drop(requester);
+    assert!(responder.recv().await.is_err());
+}
+
+#[test]
+#[timeout(1000)]
+async fn orders_by_any_ord_priority() {
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+    enum Kind {
+        Data,
+        Cancel,
+    }
+    let (requester, responder) = unbounded_priority::<usize, usize, Kind>();
+    let requests = join_all(
+        [
+            (1, Kind::Data),
+            (2, Kind::Cancel),
+            (3, Kind::Data),
+            (4, Kind::Cancel),
+        ]
+        .iter()
+        .map(|(request, kind)| requester.send_priority(*request, *kind)),
+    );
+    let handler = async {
+        while requester.len() < 4 {
+            async_std::task::yield_now().await
+        }
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            let request = responder.recv().await.unwrap();
+            order.push(*request);
+            request.respond(0).unwrap();
+        }
+        order
+    };
+    let (_, order) = join!(requests, handler);
+    // Ties are FIFO
+    assert!(order == [2, 4, 1, 3], "{:?}", order);
+}
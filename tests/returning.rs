@@ -0,0 +1,47 @@
+use async_std::test;
+use bidirectional_channel::returning::{bounded, ReturningSendError};
+use futures::join;
+
+#[test]
+async fn responded() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let (response, _) = join!(requester.send("hello"), async {
+        let request = responder.recv().await.unwrap();
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    assert!(matches!(response, Ok(5)));
+}
+
+#[test]
+async fn ignored_returns_request() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let (response, _) = join!(requester.send("hello"), async {
+        drop(responder.recv().await.unwrap())
+    });
+    assert!(matches!(
+        response,
+        Err(ReturningSendError::Ignored("hello"))
+    ));
+}
+
+#[test]
+async fn retained() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let (response, _) = join!(requester.send("hello"), async {
+        let (request, unresponded) = responder.recv().await.unwrap().into();
+        drop(unresponded);
+        request.into_inner()
+    });
+    assert!(matches!(response, Err(ReturningSendError::Retained)));
+}
+
+#[test]
+async fn closed_returns_request() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    drop(responder);
+    assert!(matches!(
+        requester.send("hello").await,
+        Err(ReturningSendError::Closed("hello"))
+    ));
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "serde")]
+use async_std::test;
+use bidirectional_channel::{bounded, ResponseTicket, Tickets};
+use futures::join;
+
+#[test]
+async fn ticket_crosses_the_wire() {
+    let (requester, responder) = bounded::<String, usize>(1);
+    let tickets = Tickets::new();
+    let (response, received) = join!(requester.request(String::from("hello")), responder.recv());
+    let (request, ticket) = received.unwrap().into_parts(&tickets);
+    let wire = serde_json::to_string(&(request, ticket)).unwrap();
+
+    // On the far side
+    let (request, ticket): (String, ResponseTicket) = serde_json::from_str(&wire).unwrap();
+    let len = request.len();
+
+    tickets.redeem(ticket).unwrap().respond(len).unwrap();
+    assert!(tickets.redeem(ticket).is_none());
+    assert!(response.unwrap().await == Ok(5));
+}
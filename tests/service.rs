@@ -0,0 +1,45 @@
+#![cfg(feature = "tower")]
+use async_std::test;
+use bidirectional_channel::{
+    bounded,
+    service::{serve, serve_fallible},
+    SendRequestError,
+};
+use futures::join;
+use std::{convert::Infallible, time::Duration};
+use tower::{service_fn, ServiceBuilder};
+
+#[test]
+async fn responds_through_middleware() {
+    let (requester, responder) = bounded::<u64, u64>(4);
+    let service = ServiceBuilder::new()
+        .concurrency_limit(2)
+        .service_fn(|n: u64| async move {
+            futures_timer::Delay::new(Duration::from_millis(n)).await;
+            Ok::<_, Infallible>(n * 2)
+        });
+    let client = async move {
+        futures::future::join_all((1..=5).map(|n| requester.send(n)))
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+    };
+    let (responses, ()) = join!(client, serve(responder, service));
+    assert_eq!(responses, vec![2, 4, 6, 8, 10]);
+}
+
+#[test]
+async fn errors_are_ignored_or_delivered() {
+    let parse = || service_fn(|s: &'static str| async move { s.parse::<u8>() });
+
+    let (requester, responder) = bounded(1);
+    let client = async move { requester.send("nope").await };
+    let (response, ()) = join!(client, serve(responder, parse()));
+    assert!(matches!(response, Err(SendRequestError::Ignored)));
+
+    let (requester, responder) = bounded(1);
+    let client = async move { requester.send_fallible("nope").await };
+    let (response, ()) = join!(client, serve_fallible(responder, parse()));
+    assert!(response.is_err());
+}
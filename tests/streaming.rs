@@ -0,0 +1,114 @@
+use async_std::test;
+use bidirectional_channel::{
+    bounded_streaming,
+    streaming::{bounded, streaming, unbounded},
+};
+use futures::{join, poll, StreamExt};
+use ntest::timeout;
+
+#[test]
+#[timeout(1000)]
+async fn backpressure() {
+    let (requester, responder) = bounded::<(), usize>(1, 2);
+    let mut responses = requester.send(()).await.unwrap();
+    let request = responder.recv().await.unwrap();
+    request.respond_backpressured(0).await.unwrap();
+    request.respond_backpressured(1).await.unwrap();
+    // The buffer is full, so this waits for the requester
+    let (sent, received) = join!(request.respond_backpressured(2), async {
+        responses.next().await
+    });
+    assert!(sent.is_ok());
+    assert!(received == Some(0));
+    drop(request);
+    assert!(responses.collect::<Vec<_>>().await == [1, 2]);
+}
+
+#[test]
+#[timeout(1000)]
+async fn abandoned_mid_stream() {
+    let (requester, responder) = bounded::<(), usize>(1, 1);
+    let responses = requester.send(()).await.unwrap();
+    let request = responder.recv().await.unwrap();
+    request.respond_backpressured(0).await.unwrap();
+    // The buffer is full, and the requester goes away while we wait
+    let (sent, _) = join!(request.respond_backpressured(1), async { drop(responses) });
+    assert!(sent == Err(1));
+    assert!(request.is_abandoned());
+}
+
+#[test]
+#[timeout(1000)]
+async fn subscription_from_another_task() {
+    let (requester, responder) = unbounded::<&str, usize>(1);
+    let events = requester.send("subscribe").await.unwrap();
+    let (topic, sender) = responder.recv().await.unwrap().split();
+    assert!(topic == "subscribe");
+    let publisher = async_std::task::spawn(async move {
+        let mut published = 0;
+        while sender.send(published).await.is_ok() {
+            published += 1;
+        }
+        published
+    });
+    // Unsubscribe
+    assert!(events.take(3).collect::<Vec<_>>().await == [0, 1, 2]);
+    assert!(publisher.await >= 3);
+}
+
+#[test]
+#[timeout(1000)]
+async fn slow_consumer_does_not_hold_up_requests() {
+    let (requester, responder) = unbounded::<usize, usize>(1);
+    let mut slow = requester.send(0).await.unwrap();
+    let request = responder.recv().await.unwrap();
+    request.respond_backpressured(0).await.unwrap();
+    let mut blocked = Box::pin(request.respond_backpressured(1));
+    assert!(poll!(blocked.as_mut()).is_pending());
+
+    // New requests still go through while the first stream is full
+    for i in 1..=3 {
+        let mut responses = requester.send(i).await.unwrap();
+        let request = responder.recv().await.unwrap();
+        request.respond_backpressured(*request).await.unwrap();
+        assert!(responses.next().await == Some(i));
+    }
+
+    assert!(slow.next().await == Some(0));
+    assert!(blocked.await.is_ok());
+    assert!(slow.next().await == Some(1));
+}
+
+#[test]
+#[timeout(1000)]
+async fn crate_root_constructor() {
+    let (requester, responder) = bounded_streaming::<usize, usize>(1, 1);
+    let (responses, request) = join!(requester.send(2), responder.recv());
+    let (count, sender) = request.unwrap().split();
+    let publisher = async move {
+        for i in 0..count {
+            sender.send(i).await.unwrap();
+        }
+    };
+    let (responses, ()) = join!(responses.unwrap().collect::<Vec<_>>(), publisher);
+    assert!(responses == [0, 1]);
+}
+
+#[test]
+#[timeout(1000)]
+async fn independently_sized_buffers() {
+    let (requester, responder) = streaming::<usize, usize>(None, 2);
+    let mut responses = requester.send(0).await.unwrap();
+    // The request queue is unbounded
+    for i in 1..=3 {
+        drop(requester.send(i).await.unwrap());
+    }
+    let request = responder.recv().await.unwrap();
+    request.respond_backpressured(0).await.unwrap();
+    request.respond_backpressured(1).await.unwrap();
+    // But the reply stream holds just two
+    let mut blocked = Box::pin(request.respond_backpressured(2));
+    assert!(poll!(blocked.as_mut()).is_pending());
+    assert!(responses.next().await == Some(0));
+    assert!(blocked.await.is_ok());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "sync")]
+use bidirectional_channel::{sync::bounded, SendRequestError};
+use std::thread;
+
+#[test]
+fn request_response() {
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let responder = thread::spawn(move || {
+        let request = responder.recv().expect("Requester was dropped");
+        let len = request.len();
+        request.respond(len).unwrap()
+    });
+    let response = requester
+        .send("hello")
+        .expect("Responder was dropped")
+        .recv()
+        .expect("UnRespondedRequest was dropped");
+    assert!(responder.join().unwrap().len() == response)
+}
+
+#[test]
+fn closed() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    drop(responder);
+    assert!(matches!(
+        requester.send("hello"),
+        Err(SendRequestError::Closed(_))
+    ))
+}
+
+#[test]
+fn ignored() {
+    let (requester, responder) = bounded::<_, usize>(1);
+    let response = requester.send("hello").unwrap();
+    drop(responder.recv());
+    assert!(matches!(response.recv(), Err(SendRequestError::Ignored)))
+}
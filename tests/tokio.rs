@@ -19,3 +19,17 @@ async fn request_response() {
     let (response, request) = join!(requester, responder);
     assert!(request.len() == response)
 }
+
+#[cfg(feature = "tokio")]
+#[test]
+async fn spawned_responder() {
+    use bidirectional_channel::spawn::{Spawn, Tokio};
+    let (requester, responder) = bounded::<&str, usize>(1);
+    Tokio.spawn(async move {
+        while let Ok(request) = responder.recv().await {
+            let len = request.len();
+            let _ = request.respond(len);
+        }
+    });
+    assert!(requester.send("hello").await.unwrap() == 5)
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "tracing")]
+
+use async_std::test;
+use bidirectional_channel::bounded;
+use futures::join;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+use tracing_core::span::Current;
+
+#[derive(Default)]
+struct Log {
+    /// Indexed by span id - 1
+    spans: Vec<Span>,
+    entered: Vec<u64>,
+    events: Vec<(String, Option<u64>)>,
+}
+
+struct Span {
+    name: &'static str,
+    metadata: &'static Metadata<'static>,
+    parent: Option<u64>,
+    blocked: Option<bool>,
+}
+
+#[derive(Default, Clone)]
+struct Recorder {
+    log: Arc<Mutex<Log>>,
+    next_id: Arc<AtomicU64>,
+}
+
+#[derive(Default)]
+struct Fields {
+    message: Option<String>,
+    blocked: Option<bool>,
+}
+
+impl Visit for Fields {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "blocked" {
+            self.blocked = Some(value)
+        }
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{:?}", value))
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut log = self.log.lock().unwrap();
+        let parent = match attributes.parent() {
+            Some(parent) => Some(parent.into_u64()),
+            None if attributes.is_contextual() => log.entered.last().copied(),
+            None => None,
+        };
+        log.spans.push(Span {
+            name: attributes.metadata().name(),
+            metadata: attributes.metadata(),
+            parent,
+            blocked: None,
+        });
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = Fields::default();
+        values.record(&mut fields);
+        let mut log = self.log.lock().unwrap();
+        if let Some(blocked) = fields.blocked {
+            log.spans[span.into_u64() as usize - 1].blocked = Some(blocked)
+        }
+    }
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let parent = event.parent().map(Id::into_u64);
+        self.log
+            .lock()
+            .unwrap()
+            .events
+            .push((fields.message.unwrap_or_default(), parent));
+    }
+    fn enter(&self, span: &Id) {
+        self.log.lock().unwrap().entered.push(span.into_u64())
+    }
+    fn exit(&self, _: &Id) {
+        self.log.lock().unwrap().entered.pop();
+    }
+    fn current_span(&self) -> Current {
+        let log = self.log.lock().unwrap();
+        match log.entered.last() {
+            Some(&id) => Current::new(Id::from_u64(id), log.spans[id as usize - 1].metadata),
+            None => Current::none(),
+        }
+    }
+}
+
+impl Recorder {
+    fn span_named(&self, name: &str) -> u64 {
+        let log = self.log.lock().unwrap();
+        let index = log.spans.iter().position(|span| span.name == name).unwrap();
+        index as u64 + 1
+    }
+    fn span(&self, id: u64) -> (Option<u64>, Option<bool>) {
+        let log = self.log.lock().unwrap();
+        let span = &log.spans[id as usize - 1];
+        (span.parent, span.blocked)
+    }
+    fn event_parent(&self, message: &str) -> Option<u64> {
+        let log = self.log.lock().unwrap();
+        log.events
+            .iter()
+            .find(|(logged, _)| logged == message)
+            .and_then(|(_, parent)| *parent)
+    }
+}
+
+#[test]
+async fn responder_events_link_back_to_the_send() {
+    let recorder = Recorder::default();
+    let _default = tracing::subscriber::set_default(recorder.clone());
+    let (requester, responder) = bounded::<&str, usize>(1);
+    let client = tracing::info_span!("client").in_scope(|| requester.send("hello"));
+    let responder = async {
+        let request = responder.recv().await.unwrap();
+        assert!(request.span().metadata().unwrap().name() == "send");
+        let len = request.len();
+        request.respond(len).unwrap()
+    };
+    let (response, _) = join!(client, responder);
+    assert!(response.unwrap() == 5);
+
+    let send = recorder.span_named("send");
+    assert!(recorder.span(send) == (Some(recorder.span_named("client")), Some(false)));
+    assert!(recorder.event_parent("received") == Some(send));
+    assert!(recorder.event_parent("responded") == Some(send));
+}
+
+#[test]
+async fn send_records_blocking_on_a_full_channel() {
+    let recorder = Recorder::default();
+    let _default = tracing::subscriber::set_default(recorder.clone());
+    let (requester, responder) = bounded::<&str, ()>(1);
+    requester.send_and_forget("first").await.unwrap();
+    let client = requester.send("second");
+    let responder = async {
+        for _ in 0..2 {
+            let request = responder.recv().await.unwrap();
+            let _ = request.respond(());
+        }
+    };
+    let (response, ()) = join!(client, responder);
+    response.unwrap();
+    assert!(recorder.span(recorder.span_named("send")).1 == Some(true));
+}